@@ -1,31 +1,122 @@
-use crate::llm::DeepSeekClient;
-use crate::web3::Web3Service;
+use crate::analytics::{ProjectAnalytics, ProjectAnalyzer};
+use crate::conversation::{Conversation, ConversationStore};
+use crate::erc20::TokenInfo;
+use crate::intent_store::{IntentRecord, IntentStore, IntentStoreError};
+use crate::intent_tools::{decode_tool_calls, intent_tool_definitions, IntentParseError};
+use crate::rule_parser;
+use crate::project_resolver::{ProjectResolver, Resolution};
+use crate::slots::{self, Choice, Clarification, IntentDraft};
+use crate::llm::{LlmProvider, Message, DEFAULT_SYSTEM_PROMPT};
+use crate::launchpad_services::{FundingPhase, MarketingInfo, Project, ProjectFetchError, ProjectStatus};
+use crate::web3::{Investment, Web3Service};
+use crate::simulation::{Simulation, SimulationVerdict};
+use crate::units::{self, Amount};
+use crate::gas::{GasFees, DEFAULT_CALL_GAS_LIMIT, DEFAULT_DEPLOY_GAS_LIMIT};
 use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
-use ethers::{abi::encode, types::U256};
+use ethers::types::{Address, Bytes, U256};
+use ethers::utils::to_checksum;
+use std::str::FromStr;
+use futures::channel::mpsc::UnboundedSender;
+use futures::StreamExt;
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub enum Intent {
     CreateProject { name: String, symbol: String },
-    Invest { project_id: String, amount: f64 },
+    Invest { project_id: String, amount: Amount },
     ClaimTokens { project_id: String },
+    ClaimRefund { project_id: String },
+    CompleteProject { project_id: String },
     GetProjectInfo { project_id: String },
+    GetProjectAnalytics { project_id: String },
     ListProjects,
+    SearchProjects {
+        query: String,
+        #[serde(default)]
+        cursor: Option<String>,
+    },
+    TrendingProjects {
+        #[serde(default = "default_listing_limit")]
+        limit: u64,
+        #[serde(default)]
+        cursor: Option<String>,
+    },
+    NewProjects {
+        #[serde(default = "default_listing_limit")]
+        limit: u64,
+        #[serde(default)]
+        cursor: Option<String>,
+    },
+    ProjectsByStatus {
+        status: ProjectStatus,
+        #[serde(default = "first_page")]
+        page: u64,
+    },
     GetUserBalance,
+    MyInvestments,
+    /// Balance of an ERC-20 token, or of a project's offering token.
+    GetTokenBalance {
+        #[serde(default)]
+        token: Option<String>,
+        #[serde(default)]
+        project_id: Option<String>,
+    },
     GetProjectStatistics,
     Unknown,
 }
 
+impl Intent {
+    /// The project this intent acts on, for intents that take a `project_id`.
+    pub fn project_id_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Intent::Invest { project_id, .. }
+            | Intent::ClaimTokens { project_id }
+            | Intent::ClaimRefund { project_id }
+            | Intent::CompleteProject { project_id }
+            | Intent::GetProjectInfo { project_id }
+            | Intent::GetProjectAnalytics { project_id } => Some(project_id),
+            Intent::GetTokenBalance { project_id: Some(project_id), .. } => Some(project_id),
+            _ => None,
+        }
+    }
+}
+
+/// Projects shown per discovery response; "more" fetches the next page.
+const DISCOVERY_PAGE_SIZE: usize = 5;
+
+fn default_listing_limit() -> u64 {
+    10
+}
+
+fn first_page() -> u64 {
+    1
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionData {
     pub to: String,
+    /// ABI-encoded calldata, 0x-prefixed hex.
     pub data: String,
+    /// Native value to send, in wei as a decimal string.
     pub value: String,
     pub chain_id: u64,
     pub description: String,
+    /// Gas limit. Set together with the fees below when the backend could
+    /// price the transaction; otherwise the wallet estimates its own.
+    #[serde(default)]
+    pub gas: Option<String>,
+    /// EIP-1559 fees in wei, as decimal strings.
+    #[serde(default)]
+    pub max_fee_per_gas: Option<String>,
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<String>,
+    /// Gas price in wei on chains that price gas the legacy way.
+    #[serde(default)]
+    pub gas_price: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,43 +128,234 @@ pub struct IntentResult {
     pub transaction_data: Option<TransactionData>,
     pub transaction_hash: Option<String>,
     pub data: Option<serde_json::Value>,
+    pub clarification: Option<Clarification>,
+    /// Pre-flight run of `transaction_data` against the latest block.
+    #[serde(default)]
+    pub simulation: Option<Simulation>,
+}
+
+impl IntentResult {
+    pub fn status(&self) -> &'static str {
+        if self.clarification.is_some() {
+            "needs_clarification"
+        } else if self.success {
+            "completed"
+        } else {
+            "failed"
+        }
+    }
+}
+
+/// Pipeline progress reported to streaming clients, in the order the stages run.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum IntentEvent {
+    Parsing { intent_id: String },
+    Parsed { intent: Intent },
+    Executing,
+    TxPrepared { transaction_data: TransactionData },
+    NeedsClarification { clarification: Box<Clarification> },
+    Token { text: String },
+    Completed { result: Box<IntentResult> },
+    Failed { message: String },
+}
+
+impl IntentEvent {
+    pub fn stage(&self) -> &'static str {
+        match self {
+            IntentEvent::Parsing { .. } => "parsing",
+            IntentEvent::Parsed { .. } => "parsed",
+            IntentEvent::Executing => "executing",
+            IntentEvent::TxPrepared { .. } => "tx_prepared",
+            IntentEvent::NeedsClarification { .. } => "needs_clarification",
+            IntentEvent::Token { .. } => "token",
+            IntentEvent::Completed { .. } => "completed",
+            IntentEvent::Failed { .. } => "failed",
+        }
+    }
+}
+
+/// Who is asking, and which conversation the request belongs to.
+#[derive(Debug, Clone)]
+pub struct UserContext {
+    pub user_id: i64,
+    pub conversation_id: String,
+    /// Wallet that will sign prepared transactions, when one is known.
+    pub wallet_address: Option<String>,
+}
+
+impl UserContext {
+    pub fn new(user_id: i64, session_id: Option<String>) -> Self {
+        let conversation_id = session_id.unwrap_or_else(|| format!("user-{}", user_id));
+        Self { user_id, conversation_id, wallet_address: None }
+    }
+    
+    pub fn with_wallet(mut self, wallet_address: Option<String>) -> Self {
+        self.wallet_address = wallet_address;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct IntentAgent {
     pub name: String,
-    llm_client: DeepSeekClient,
+    llm_client: Arc<dyn LlmProvider>,
     web3_service: Web3Service,
-    active_intents: HashMap<String, IntentResult>,
+    project_resolver: ProjectResolver,
+    analyzer: ProjectAnalyzer,
+    conversations: ConversationStore,
+    intents: IntentStore,
 }
 
 impl IntentAgent {
-    pub fn new(name: &str, api_key: &str, web3_service: Web3Service) -> Self {
+    pub fn new(
+        name: &str,
+        llm_client: Arc<dyn LlmProvider>,
+        web3_service: Web3Service,
+        conversations: ConversationStore,
+        intents: IntentStore,
+    ) -> Self {
         Self {
             name: name.to_string(),
-            llm_client: DeepSeekClient::new(api_key),
+            llm_client,
+            project_resolver: ProjectResolver::new(web3_service.clone()),
+            analyzer: ProjectAnalyzer::new(web3_service.clone()),
             web3_service,
-            active_intents: HashMap::new(),
+            conversations,
+            intents,
         }
     }
     
-    pub async fn process_user_intent(&self, user_id: i64, user_input: &str) -> Result<IntentResult> {
+    /// Raise analytics for a project, as served by the analytics endpoint.
+    pub async fn project_analytics(&self, project_id: &str) -> Result<ProjectAnalytics> {
+        self.analyzer.analyze(project_id, unix_now()).await
+    }
+    
+    /// Every investment `wallet` made through the launchpad, oldest first.
+    pub async fn investments(&self, wallet: &str) -> Result<Vec<Investment>> {
+        self.web3_service.get_user_investments(wallet).await
+    }
+    
+    pub async fn process_user_intent(&self, ctx: &UserContext, user_input: &str) -> Result<IntentResult> {
+        self.run_pipeline(ctx, user_input, None).await
+    }
+    
+    /// Same pipeline as `process_user_intent`, reporting each stage and the
+    /// AI response tokens on `events` as they happen.
+    pub async fn process_user_intent_streaming(
+        &self,
+        ctx: &UserContext,
+        user_input: &str,
+        events: &UnboundedSender<IntentEvent>,
+    ) -> Result<IntentResult> {
+        self.run_pipeline(ctx, user_input, Some(events)).await
+    }
+    
+    async fn run_pipeline(
+        &self,
+        ctx: &UserContext,
+        user_input: &str,
+        events: Option<&UnboundedSender<IntentEvent>>,
+    ) -> Result<IntentResult> {
+        let emit = |event: IntentEvent| {
+            if let Some(events) = events {
+                let _ = events.unbounded_send(event);
+            }
+        };
+        
         let intent_id = Uuid::new_v4().to_string();
-        println!("🎯 Processing intent from user {}: {}", user_id, user_input);
+        println!("🎯 Processing intent from user {}: {}", ctx.user_id, user_input);
         println!("📝 Created intent: {}", intent_id);
         
-        // Parse intent using LLM
+        let conversation = self.conversations.get(&ctx.conversation_id).await;
+        let history = conversation
+            .as_ref()
+            .map(Conversation::prompt_messages)
+            .unwrap_or_default();
+        println!("💬 Conversation {}: {} history messages", ctx.conversation_id, history.len());
+        
+        // Parse intent using LLM, or fill in the slots we asked about last turn
         println!("🧠 Parsing intent with LLM: {}", intent_id);
-        let parsed_intent = self.parse_intent_with_llm(user_input).await?;
+        emit(IntentEvent::Parsing { intent_id: intent_id.clone() });
+        let pending = conversation.as_ref().and_then(|c| c.pending.clone());
+        let next_page = conversation
+            .as_ref()
+            .and_then(|c| c.entities.next_page.clone())
+            .filter(|_| rule_parser::is_more_request(user_input));
+        let draft = match (&pending, next_page) {
+            (Some(clarification), _) => self.continue_clarification(clarification, user_input, &history).await?,
+            (None, Some(next_page)) => {
+                println!("📄 Continuing listing: {:?}", next_page);
+                next_page
+            }
+            (None, None) => self.parse_intent(user_input, &history).await?,
+        };
+        
+        // Whatever this turn becomes, it answered the question asked last turn
+        if let Some(asked_in) = conversation.as_ref().and_then(|c| c.pending_intent_id.as_deref()) {
+            self.track(self.intents.mark_superseded(asked_in, &intent_id).await);
+        }
+        
+        let resolved = match slots::resolve(draft) {
+            Ok(intent) => self.resolve_project_reference(intent).await?,
+            Err(clarification) => Err(clarification),
+        };
+        let parsed_intent = match resolved {
+            Ok(intent) => intent,
+            Err(clarification) => {
+                println!("❓ Needs clarification for {}: {:?}", clarification.intent, clarification.open_fields());
+                let result = clarification_result(&intent_id, &clarification);
+                self.intents.record_parsed(&intent_id, &ctx.conversation_id, ctx.user_id, user_input, None).await;
+                self.track(self.intents.record_result(&result).await);
+                emit(IntentEvent::NeedsClarification { clarification: clarification.clone() });
+                emit(IntentEvent::Token { text: result.ai_message.clone() });
+                self.conversations.record(&ctx.conversation_id, user_input, None, &result).await;
+                self.conversations.set_pending(&ctx.conversation_id, Some((intent_id.clone(), *clarification))).await;
+                emit(IntentEvent::Completed { result: Box::new(result.clone()) });
+                return Ok(result);
+            }
+        };
+        if pending.is_some() {
+            self.conversations.set_pending(&ctx.conversation_id, None).await;
+        }
         println!("✅ Parsed intent: {:?}", parsed_intent);
+        self.intents.record_parsed(&intent_id, &ctx.conversation_id, ctx.user_id, user_input, Some(&parsed_intent)).await;
+        emit(IntentEvent::Parsed { intent: parsed_intent.clone() });
         
         // Execute intent
         println!("🚀 Executing intent: {:?}", parsed_intent);
-        let result = self.execute_intent(&parsed_intent, user_input).await?;
+        emit(IntentEvent::Executing);
+        let mut result = match self.execute_intent(ctx, &parsed_intent, user_input).await {
+            Ok(result) => result,
+            Err(e) => {
+                self.track(self.intents.mark_failed(&intent_id, &e.to_string()).await);
+                return Err(e);
+            }
+        };
+        if let Some(tx_data) = &result.transaction_data {
+            let simulation = self.simulate(ctx, tx_data).await;
+            println!("🧪 Simulation: {:?} {}", simulation.verdict, simulation.reason.as_deref().unwrap_or(""));
+            // Nothing to sign if it is already known to revert
+            if simulation.reverts() {
+                result.success = false;
+                result.message = format!(
+                    "This transaction would fail: {}",
+                    simulation.reason.as_deref().unwrap_or("the contract rejected it")
+                );
+                result.transaction_data = None;
+            }
+            result.simulation = Some(simulation);
+        }
+        if let Some(tx_data) = &result.transaction_data {
+            emit(IntentEvent::TxPrepared { transaction_data: tx_data.clone() });
+        }
         
         // Generate AI response
         println!("🤖 Generating AI response for intent result...");
-        let ai_message = self.generate_ai_response(&result).await?;
+        let ai_message = match events {
+            Some(events) => self.stream_ai_response(&result, &history, events).await?,
+            None => self.generate_ai_response(&result, &history).await?,
+        };
         println!("✅ AI response generated successfully");
         
         let final_result = IntentResult {
@@ -84,116 +366,181 @@ impl IntentAgent {
             transaction_data: result.transaction_data,
             transaction_hash: result.transaction_hash,
             data: result.data,
+            clarification: None,
+            simulation: result.simulation,
         };
         
-        // Store the intent (optional)
-        // self.active_intents.insert(intent_id, final_result.clone());
+        self.track(self.intents.record_result(&final_result).await);
+        
+        self.conversations
+            .record(&ctx.conversation_id, user_input, Some(&parsed_intent), &final_result)
+            .await;
         
+        emit(IntentEvent::Completed { result: Box::new(final_result.clone()) });
         Ok(final_result)
     }
     
-    async fn parse_intent_with_llm(&self, user_input: &str) -> Result<Intent> {
-        let prompt = format!(
-            "Analyze the user's intent from their message. Classify it into one of these categories:
-            1. CreateProject - When user wants to create a new token/project
-            2. Invest - When user wants to invest in a project
-            3. ClaimTokens - When user wants to claim tokens from a project
-            4. GetProjectInfo - When user wants information about a project
-            5. ListProjects - When user wants to see available projects
-            6. GetUserBalance - When user wants to check their wallet balance
-            7. GetProjectStatistics - When user wants statistics about projects
-            8. Unknown - If none of the above match
-            
-            User message: \"{}\"
-            
-            Respond with ONLY the category name and any extracted parameters in JSON format.
-            Example responses:
-            - {{\"intent\": \"CreateProject\", \"name\": \"MyToken\", \"symbol\": \"MTK\"}}
-            - {{\"intent\": \"Invest\", \"project_id\": \"0x123...\", \"amount\": 0.5}}
-            - {{\"intent\": \"GetUserBalance\"}}
-            
-            If parameters can't be extracted, use null or best guess.
-            ",
-            user_input
-        );
+    /// Rule parser first for unambiguous commands, then the LLM, then the rule
+    /// parser's best guess if the LLM can't be reached.
+    async fn parse_intent(&self, user_input: &str, history: &[Message]) -> Result<IntentDraft> {
+        let rule_parse = rule_parser::parse_intent(user_input);
+        if let Some(parse) = &rule_parse {
+            if parse.confident {
+                println!("⚡ Rule parser matched: {:?}", parse.draft);
+                return Ok(parse.draft.clone());
+            }
+        }
         
-        let response = self.llm_client.generate_response(&prompt).await?;
-        
-        // Parse the JSON response
-        let json_start = response.find('{').unwrap_or(0);
-        let json_end = response.rfind('}').map(|i| i + 1).unwrap_or(response.len());
-        let json_str = &response[json_start..json_end];
-        
-        let parsed: serde_json::Value = serde_json::from_str(json_str)
-            .map_err(|e| anyhow!("Failed to parse LLM response as JSON: {}", e))?;
-        
-        let intent_str = parsed.get("intent")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown");
-        
-        match intent_str {
-            "CreateProject" => {
-                let name = parsed.get("name")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                let symbol = parsed.get("symbol")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                Ok(Intent::CreateProject { name, symbol })
-            },
-            "Invest" => {
-                let project_id = parsed.get("project_id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                let amount = parsed.get("amount")
-                    .and_then(|v| v.as_f64())
-                    .unwrap_or(0.0);
-                Ok(Intent::Invest { project_id, amount })
-            },
-            "ClaimTokens" => {
-                let project_id = parsed.get("project_id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                Ok(Intent::ClaimTokens { project_id })
-            },
-            "GetProjectInfo" => {
-                let project_id = parsed.get("project_id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                Ok(Intent::GetProjectInfo { project_id })
-            },
-            "ListProjects" => Ok(Intent::ListProjects),
-            "GetUserBalance" => Ok(Intent::GetUserBalance),
-            "GetProjectStatistics" => Ok(Intent::GetProjectStatistics),
-            _ => Ok(Intent::Unknown),
+        match self.parse_intent_with_llm(user_input, history).await {
+            Ok(draft) => Ok(draft),
+            Err(e) if e.downcast_ref::<IntentParseError>().is_some() => Err(e),
+            Err(e) => {
+                println!("⚠️ LLM unavailable ({}), falling back to rule parser", e);
+                Ok(rule_parse.map(|parse| parse.draft).unwrap_or_else(IntentDraft::unknown))
+            }
+        }
+    }
+    
+    /// Treats the user's reply as an answer to the pending clarification,
+    /// unless it is clearly a different request.
+    async fn continue_clarification(
+        &self,
+        clarification: &Clarification,
+        user_input: &str,
+        history: &[Message],
+    ) -> Result<IntentDraft> {
+        let mut draft = slots::fill_from_reply(clarification, user_input);
+        
+        if let Some(parse) = rule_parser::parse_intent(user_input) {
+            if parse.draft.intent != clarification.intent {
+                println!("↪️ New request replaces pending {}", clarification.intent);
+                return self.parse_intent(user_input, history).await;
+            }
+            draft.merge(&parse.draft);
+            return Ok(draft);
+        }
+        if draft != clarification.draft {
+            return Ok(draft);
         }
+        
+        // Nothing we could pick out directly: let the LLM read the reply
+        // against the question it answers
+        let parsed = self.parse_intent(user_input, history).await?;
+        if parsed.intent == clarification.intent {
+            draft.merge(&parsed);
+            Ok(draft)
+        } else if parsed.intent == "Unknown" {
+            Ok(draft)
+        } else {
+            println!("↪️ New request replaces pending {}", clarification.intent);
+            Ok(parsed)
+        }
+    }
+    
+    /// Replaces a project name or symbol in `intent` with the project's
+    /// address, or says what to ask when it matches nothing or several projects.
+    async fn resolve_project_reference(&self, mut intent: Intent) -> Result<Result<Intent, Box<Clarification>>> {
+        let Some(reference) = intent.project_id_mut().map(|id| id.clone()) else {
+            return Ok(Ok(intent));
+        };
+        
+        match self.project_resolver.resolve(&reference).await? {
+            Resolution::Found { address, project } => {
+                if let Some(project) = project {
+                    println!("🎯 '{}' is {} ({}) at {}", reference, project.name, project.symbol, address);
+                }
+                if let Some(project_id) = intent.project_id_mut() {
+                    *project_id = address;
+                }
+                Ok(Ok(intent))
+            }
+            Resolution::Ambiguous(matches) => {
+                let choices = matches
+                    .iter()
+                    .map(|m| Choice {
+                        value: to_checksum(&m.project.address, None),
+                        label: format!("{} ({}) — {:?}", m.project.name, m.project.symbol, m.project.address),
+                    })
+                    .collect();
+                let reason = format!("Several projects match \"{}\"", reference);
+                Ok(Err(slots::reask_slot(IntentDraft::from_intent(&intent), "project_id", &reason, choices)))
+            }
+            Resolution::NotFound => {
+                let reason = format!("I couldn't find a project called \"{}\"", reference);
+                Ok(Err(slots::reask_slot(IntentDraft::from_intent(&intent), "project_id", &reason, Vec::new())))
+            }
+        }
+    }
+    
+    async fn parse_intent_with_llm(&self, user_input: &str, history: &[Message]) -> Result<IntentDraft> {
+        let system_prompt = "You are the intent parser for Teemah AI, a Web3 launchpad assistant. \
+            Map the user's message to exactly one of the provided tools, filling its arguments from the message. \
+            Never invent project addresses or amounts; leave out any argument the user has not given and the agent will ask for it. \
+            Use the conversation history to resolve references such as \"that one\" or \"the second project\" to concrete addresses. \
+            If the message does not match any tool, reply with a short plain-text note and call no tool.";
+        
+        let mut messages = vec![Message::system(system_prompt)];
+        messages.extend_from_slice(history);
+        messages.push(Message::user(user_input));
+        
+        let completion = self.llm_client
+            .chat_with_tools(messages, &intent_tool_definitions())
+            .await?;
+        
+        if completion.tool_calls.is_empty() {
+            println!("🤷 LLM made no tool call: {}", completion.content.as_deref().unwrap_or(""));
+        }
+        
+        Ok(decode_tool_calls(&completion.tool_calls)?)
     }
     
-    async fn execute_intent(&self, intent: &Intent, original_input: &str) -> Result<IntentResult> {
+    async fn execute_intent(&self, ctx: &UserContext, intent: &Intent, original_input: &str) -> Result<IntentResult> {
         match intent {
             Intent::CreateProject { name, symbol } => {
-                self.execute_create_project(name, symbol).await
+                self.execute_create_project(ctx, name, symbol).await
             },
             Intent::Invest { project_id, amount } => {
-                self.execute_invest(project_id, *amount).await
+                self.execute_invest(ctx, project_id, *amount).await
             },
             Intent::ClaimTokens { project_id } => {
-                self.execute_claim_tokens(project_id).await
+                self.execute_claim_tokens(ctx, project_id).await
+            },
+            Intent::ClaimRefund { project_id } => {
+                self.execute_claim_refund(ctx, project_id).await
+            },
+            Intent::CompleteProject { project_id } => {
+                self.execute_complete_project(ctx, project_id).await
             },
             Intent::GetProjectInfo { project_id } => {
                 self.execute_get_project_info(project_id).await
             },
+            Intent::GetProjectAnalytics { project_id } => {
+                self.execute_get_project_analytics(project_id).await
+            },
             Intent::ListProjects => {
                 self.execute_list_projects().await
             },
+            Intent::SearchProjects { query, cursor } => {
+                self.execute_search_projects(query, cursor.as_deref()).await
+            },
+            Intent::TrendingProjects { limit, cursor } => {
+                self.execute_trending_projects(*limit, cursor.as_deref()).await
+            },
+            Intent::NewProjects { limit, cursor } => {
+                self.execute_new_projects(*limit, cursor.as_deref()).await
+            },
+            Intent::ProjectsByStatus { status, page } => {
+                self.execute_projects_by_status(*status, *page).await
+            },
             Intent::GetUserBalance => {
                 self.execute_get_user_balance().await
             },
+            Intent::MyInvestments => {
+                self.execute_my_investments(ctx).await
+            },
+            Intent::GetTokenBalance { token, project_id } => {
+                self.execute_get_token_balance(ctx, token.as_deref(), project_id.as_deref()).await
+            },
             Intent::GetProjectStatistics => {
                 self.execute_get_project_statistics().await
             },
@@ -205,76 +552,56 @@ impl IntentAgent {
                     ai_message: String::new(),
                     transaction_data: None,
                     transaction_hash: None,
+                    clarification: None,
+                    simulation: None,
                     data: None,
                 })
             },
         }
     }
 
-    async fn execute_create_project(&self, name: &str, symbol: &str) -> Result<IntentResult> {
+    async fn execute_create_project(&self, ctx: &UserContext, name: &str, symbol: &str) -> Result<IntentResult> {
      println!("🏗️ Creating project: {} ({})", name, symbol);
     
-     // Get the actual chain ID from web3_service
+     // The service was built for one registry chain, so its id is always set
      let chain_id = self.web3_service.chain_id();
      println!("🔗 Using chain ID: {}", chain_id);
     
-     // Get contract address
-     let contract_address = self.web3_service.get_contract_address().await
-        .unwrap_or_else(|_| "0x0000000000000000000000000000000000000000".to_string());
-    
-     println!("📜 Contract address: {}", contract_address);
-    
-     // IMPORTANT: Make sure chain_id is not 0
-     if chain_id == 0 {
-        println!("⚠️ WARNING: Chain ID is 0, defaulting to 97 (BSC Testnet)");
-     }
-    
-     let final_chain_id = if chain_id == 0 { 97 } else { chain_id };
-    
-     // Create proper contract call data for createProjectWithTokenViaTelegram
-     // Function signature: createProjectWithTokenViaTelegram(address, string, string, string, uint8, uint256, uint256, uint256, uint256, uint256, uint256, uint256, uint16, uint16, uint256)
-    
-     // Get default values for parameters (you might want to make these configurable)
-     let creator_address = "0x0000000000000000000000000000000000000000".to_string(); // Will be replaced by user's wallet
+     // The contract records the creator explicitly, so use the signing wallet when we know it
+     let creator = match &ctx.wallet_address {
+        Some(address) => Address::from_str(address)
+            .map_err(|e| anyhow!("Invalid wallet address {}: {}", address, e))?,
+        None => {
+            println!("⚠️ No wallet address known, using the zero address as creator");
+            Address::zero()
+        }
+     };
      let token_name = format!("{} Token", name);
      let token_decimals = 18u8;
      let initial_supply = U256::from(1_000_000_000_000_000_000_000u128); // 1000 tokens with 18 decimals
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-     let soft_cap = U256::from(1_000_000_000_000_000_000u128); // 1 ETH
-     let hard_cap = U256::from(10_000_000_000_000_000_000u128); // 10 ETH
-     let start_time = U256::from(now + 60); // Start in 1 minute
-    let end_time = U256::from(now + (30 * 24 * 60 * 60)); // End in 30 days
-     let token_price = U256::from(100_000_000_000_000u128); // 0.0001 ETH per token
-     let tokens_for_sale = initial_supply * U256::from(70) / U256::from(100); // 70% of tokens for sale
-     let liquidity_percent = 3000u16; // 30%
-     let marketing_percent = 500u16; // 5%
-     let marketing_telegram_id = U256::from(0u64); // Default to 0
     
      println!("📊 Project parameters:");
-     println!("   Creator: {}", creator_address);
+     println!("   Creator: {:?}", creator);
      println!("   Token Name: {}", token_name);
      println!("   Decimals: {}", token_decimals);
      println!("   Initial Supply: {}", initial_supply);
-     println!("   Soft Cap: {}", soft_cap);
-     println!("   Hard Cap: {}", hard_cap);
-     println!("   Start Time: {}", start_time);
-     println!("   End Time: {}", end_time);
-     println!("   Token Price: {}", token_price);
-     println!("   Tokens for Sale: {}", tokens_for_sale);
-     println!("   Liquidity %: {}", liquidity_percent);
-     println!("   Marketing %: {}", marketing_percent);
-    
-     // Create transaction data
-     let tx_data = TransactionData {
-        to: contract_address.clone(),
-        data: "0x".to_string(), // We'll handle encoding client-side for now
-        value: "0".to_string(),
-        chain_id: final_chain_id,
-        description: format!("Create {} token with symbol {}", name, symbol),
-     };
+    
+     let calldata = self.web3_service.create_project_calldata(
+        creator,
+        &token_name,
+        symbol,
+        token_decimals,
+        initial_supply,
+     )?;
+     let contract_address = format!("{:?}", self.web3_service.launchpad_address());
+    
+     let tx_data = self.launchpad_transaction(
+        ctx,
+        calldata,
+        U256::zero(),
+        DEFAULT_DEPLOY_GAS_LIMIT,
+        format!("Create {} token with symbol {}", name, symbol),
+     ).await;
     
      println!("📝 Created transaction data:");
      println!("   To: {}", tx_data.to);
@@ -288,31 +615,23 @@ impl IntentAgent {
         ai_message: String::new(),
         transaction_data: Some(tx_data),
         transaction_hash: None,
+        clarification: None,
+        simulation: None,
         data: Some(serde_json::json!({
             "action": "create_project",
             "name": name,
             "symbol": symbol,
             "contract_address": contract_address,
-            "chain_id": final_chain_id,
+            "chain_id": chain_id,
             "requires_signing": true,
             "is_contract_call": true,
             "function_name": "createProjectWithTokenViaTelegram",
             "parameters": {
-                "creator": creator_address,
-                "project_name": name,
+                "creator": format!("{:?}", creator),
                 "token_name": token_name,
                 "token_symbol": symbol,
                 "token_decimals": token_decimals,
-                "initial_supply": initial_supply.to_string(),
-                "soft_cap": soft_cap.to_string(),
-                "hard_cap": hard_cap.to_string(),
-                "start_time": start_time.to_string(),
-                "end_time": end_time.to_string(),
-                "token_price": token_price.to_string(),
-                "tokens_for_sale": tokens_for_sale.to_string(),
-                "liquidity_percent": liquidity_percent,
-                "marketing_percent": marketing_percent,
-                "marketing_telegram_id": marketing_telegram_id.to_string()
+                "initial_supply": initial_supply.to_string()
              }
             })),
         })
@@ -375,17 +694,33 @@ impl IntentAgent {
     //      })),
     //     })
     // }
-    async fn execute_invest(&self, project_id: &str, amount: f64) -> Result<IntentResult> {
+    async fn execute_invest(&self, ctx: &UserContext, project_id: &str, amount: Amount) -> Result<IntentResult> {
         println!("💰 Investing {} in project: {}", amount, project_id);
         
-        let tx_data = TransactionData {
-            to: project_id.to_string(),
-            data: format!("invest:{}", amount),
-            value: (amount * 1e18).to_string(), // Convert ETH to wei
-            chain_id: self.web3_service.chain_id(),
-            description: format!("Invest {} ETH in project {}", amount, project_id),
+        let project = self.load_project(project_id).await?;
+        let now = unix_now();
+        if let Some(reason) = project.invest_refusal(now) {
+            return Ok(ineligible_result("invest", project_id, reason, &project, now));
+        }
+        
+        // invest() is payable on the launchpad; the project is an argument, the amount is msg.value
+        let calldata = self.web3_service.invest_calldata(project_id)?;
+        let value = match amount {
+            Amount::Exact(wei) => wei,
+            Amount::All => match self.spendable_balance(ctx, &calldata).await? {
+                Ok(wei) => wei,
+                Err(reason) => return Ok(ineligible_result("invest", project_id, &reason, &project, now)),
+            },
         };
         
+        let tx_data = self.launchpad_transaction(
+            ctx,
+            calldata,
+            value,
+            DEFAULT_CALL_GAS_LIMIT,
+            format!("Invest {} {} in project {}", units::format_ether(value), self.web3_service.native_symbol(), project_id),
+        ).await;
+        
         Ok(IntentResult {
             intent_id: Uuid::new_v4().to_string(),
             success: true,
@@ -393,25 +728,31 @@ impl IntentAgent {
             ai_message: String::new(),
             transaction_data: Some(tx_data),
             transaction_hash: None,
+            clarification: None,
+            simulation: None,
             data: Some(serde_json::json!({
                 "action": "invest",
                 "project_id": project_id,
-                "amount": amount,
+                "amount": units::format_ether(value),
+                "amount_wei": value.to_string(),
+                "project": project_json(&project, now),
                 "requires_signing": true,
             })),
         })
     }
     
-    async fn execute_claim_tokens(&self, project_id: &str) -> Result<IntentResult> {
+    async fn execute_claim_tokens(&self, ctx: &UserContext, project_id: &str) -> Result<IntentResult> {
         println!("🎫 Claiming tokens from project: {}", project_id);
         
-        let tx_data = TransactionData {
-            to: project_id.to_string(),
-            data: "claimTokens".to_string(),
-            value: "0".to_string(),
-            chain_id: self.web3_service.chain_id(),
-            description: format!("Claim tokens from project {}", project_id),
-        };
+        let calldata = self.web3_service.claim_tokens_calldata(project_id)?;
+        
+        let tx_data = self.launchpad_transaction(
+            ctx,
+            calldata,
+            U256::zero(),
+            DEFAULT_CALL_GAS_LIMIT,
+            format!("Claim tokens from project {}", project_id),
+        ).await;
         
         Ok(IntentResult {
             intent_id: Uuid::new_v4().to_string(),
@@ -420,6 +761,8 @@ impl IntentAgent {
             ai_message: String::new(),
             transaction_data: Some(tx_data),
             transaction_hash: None,
+            clarification: None,
+            simulation: None,
             data: Some(serde_json::json!({
                 "action": "claim_tokens",
                 "project_id": project_id,
@@ -428,21 +771,206 @@ impl IntentAgent {
         })
     }
     
+    async fn execute_claim_refund(&self, ctx: &UserContext, project_id: &str) -> Result<IntentResult> {
+        println!("💸 Claiming refund from project: {}", project_id);
+        
+        let project = self.load_project(project_id).await?;
+        let now = unix_now();
+        let phase = project.phase(now);
+        println!("📊 Status {:?}, funding phase {:?} (raised {} / soft cap {})", project.status, phase, project.total_raised, project.soft_cap);
+        
+        if let Some(reason) = project.refund_refusal(now) {
+            return Ok(ineligible_result("claim_refund", project_id, reason, &project, now));
+        }
+        
+        let calldata = self.web3_service.claim_refund_calldata(project_id)?;
+        
+        let tx_data = self.launchpad_transaction(
+            ctx,
+            calldata,
+            U256::zero(),
+            DEFAULT_CALL_GAS_LIMIT,
+            format!("Claim refund from project {}", project_id),
+        ).await;
+        
+        Ok(IntentResult {
+            intent_id: Uuid::new_v4().to_string(),
+            success: true,
+            message: "Transaction prepared for refund claim".to_string(),
+            ai_message: String::new(),
+            transaction_data: Some(tx_data),
+            transaction_hash: None,
+            clarification: None,
+            simulation: None,
+            data: Some(serde_json::json!({
+                "action": "claim_refund",
+                "project_id": project_id,
+                "project": project_json(&project, now),
+                "requires_signing": true,
+            })),
+        })
+    }
+    
+    async fn execute_complete_project(&self, ctx: &UserContext, project_id: &str) -> Result<IntentResult> {
+        println!("🏁 Completing project: {}", project_id);
+        
+        let project = self.load_project(project_id).await?;
+        let now = unix_now();
+        let phase = project.phase(now);
+        println!("📊 Status {:?}, funding phase {:?} (raised {} / soft cap {})", project.status, phase, project.total_raised, project.soft_cap);
+        
+        if let Some(reason) = project.complete_refusal(now) {
+            return Ok(ineligible_result("complete_project", project_id, reason, &project, now));
+        }
+        
+        let calldata = self.web3_service.complete_project_calldata(project_id)?;
+        
+        let tx_data = self.launchpad_transaction(
+            ctx,
+            calldata,
+            U256::zero(),
+            DEFAULT_CALL_GAS_LIMIT,
+            format!("Complete project {}", project_id),
+        ).await;
+        
+        Ok(IntentResult {
+            intent_id: Uuid::new_v4().to_string(),
+            success: true,
+            message: "Transaction prepared for project completion".to_string(),
+            ai_message: String::new(),
+            transaction_data: Some(tx_data),
+            transaction_hash: None,
+            clarification: None,
+            simulation: None,
+            data: Some(serde_json::json!({
+                "action": "complete_project",
+                "project_id": project_id,
+                "project": project_json(&project, now),
+                "requires_signing": true,
+            })),
+        })
+    }
+    
+    /// A launchpad call for the user's wallet to sign, with the gas limit and
+    /// the chain's normal fees attached. Estimated from the connected wallet
+    /// when there is one; if pricing fails the wallet prices it instead.
+    async fn launchpad_transaction(
+        &self,
+        ctx: &UserContext,
+        calldata: Bytes,
+        value: U256,
+        fallback_gas_limit: u64,
+        description: String,
+    ) -> TransactionData {
+        let from = ctx.wallet_address.as_deref().and_then(|address| Address::from_str(address).ok());
+        let mut tx_data = TransactionData {
+            to: format!("{:?}", self.web3_service.launchpad_address()),
+            data: calldata.to_string(),
+            value: value.to_string(),
+            chain_id: self.web3_service.chain_id(),
+            description,
+            gas: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas_price: None,
+        };
+        
+        match self.web3_service.plan_gas(from, &calldata, value, fallback_gas_limit).await {
+            Ok(plan) => {
+                println!("⛽ Gas limit {} ({}), {}", plan.gas_limit, if plan.estimated { "estimated" } else { "default" }, plan.fees);
+                tx_data.gas = Some(plan.gas_limit.to_string());
+                match plan.fees {
+                    GasFees::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas } => {
+                        tx_data.max_fee_per_gas = Some(max_fee_per_gas.to_string());
+                        tx_data.max_priority_fee_per_gas = Some(max_priority_fee_per_gas.to_string());
+                    }
+                    GasFees::Legacy { gas_price } => tx_data.gas_price = Some(gas_price.to_string()),
+                }
+            }
+            Err(e) => println!("⚠️ Could not price transaction, leaving gas to the wallet: {}", e),
+        }
+        tx_data
+    }
+    
+    /// The connected wallet's balance less the most the call's gas can cost,
+    /// for "invest all". The inner error says why nothing can be spent.
+    async fn spendable_balance(&self, ctx: &UserContext, calldata: &Bytes) -> Result<std::result::Result<U256, String>> {
+        let Some(from) = ctx.wallet_address.as_deref().and_then(|address| Address::from_str(address).ok()) else {
+            return Ok(Err("Connect your wallet so I can see how much you have to invest.".to_string()));
+        };
+        let balance = self.web3_service.native_balance(from).await?;
+        // Priced without a value, which the estimate would otherwise count against the balance
+        let gas = self.web3_service.plan_gas(Some(from), calldata, U256::zero(), DEFAULT_CALL_GAS_LIMIT).await?;
+        let reserve = gas.max_cost();
+        let symbol = self.web3_service.native_symbol();
+        println!("💰 Balance {} {symbol}, keeping {} {symbol} for gas", units::format_ether(balance), units::format_ether(reserve));
+        match balance.checked_sub(reserve).filter(|spendable| !spendable.is_zero()) {
+            Some(spendable) => Ok(Ok(spendable)),
+            None => Ok(Err(format!(
+                "Your balance of {} {} does not cover the gas for this investment.",
+                units::format_ether(balance),
+                symbol
+            ))),
+        }
+    }
+    
+    /// Runs the prepared transaction from the connected wallet, which is the
+    /// account that will sign it.
+    async fn simulate(&self, ctx: &UserContext, tx_data: &TransactionData) -> Simulation {
+        let Some(from) = ctx.wallet_address.as_deref().and_then(|address| Address::from_str(address).ok()) else {
+            return Simulation::unknown("No wallet connected to simulate the transaction from");
+        };
+        let data = match Bytes::from_str(&tx_data.data) {
+            Ok(data) => data,
+            Err(e) => return Simulation::unknown(format!("Invalid calldata: {}", e)),
+        };
+        let value = U256::from_dec_str(&tx_data.value).unwrap_or_default();
+        self.web3_service.simulate(from, &data, value).await
+    }
+    
+    /// The project's current figures, read through the RPC quorum since a
+    /// transaction is prepared on them; unlike `get_project`, a missing
+    /// project is an error.
+    async fn load_project(&self, project_id: &str) -> Result<Project> {
+        self.web3_service
+            .get_project_verified(project_id)
+            .await?
+            .ok_or_else(|| anyhow!("Could not load project {}", project_id))
+    }
+    
     async fn execute_get_project_info(&self, project_id: &str) -> Result<IntentResult> {
         println!("📊 Getting project info: {}", project_id);
         
         // This is a read-only operation, no transaction needed
         match self.web3_service.get_project(project_id).await {
             Ok(Some(project)) => {
+                let now = unix_now();
+                let token = self.offering_token(&project).await;
+                let marketing = self.web3_service.get_project_marketing_info(project_id).await.unwrap_or_else(|e| {
+                    println!("⚠️ Could not read marketing info of {}: {}", project_id, e);
+                    None
+                });
+                let symbol = self.web3_service.native_symbol();
+                let mut message = describe_project(&project, now, symbol);
+                if let Some(token) = &token {
+                    message += &format!(" {}", describe_token(token));
+                }
+                if let Some(marketing) = &marketing {
+                    message += &format!(" {}", describe_marketing(marketing, symbol));
+                }
                 Ok(IntentResult {
                     intent_id: Uuid::new_v4().to_string(),
                     success: true,
-                    message: "Project information retrieved".to_string(),
+                    message,
                     ai_message: String::new(),
                     transaction_data: None,
                     transaction_hash: None,
+                    clarification: None,
+                    simulation: None,
                     data: Some(serde_json::json!({
-                        "project": project,
+                        "project": project_json(&project, now),
+                        "token": token.as_ref().map(token_json),
+                        "marketing": marketing.as_ref().map(marketing_json),
                         "action": "get_project_info",
                         "requires_signing": false,
                     })),
@@ -456,6 +984,8 @@ impl IntentAgent {
                     ai_message: String::new(),
                     transaction_data: None,
                     transaction_hash: None,
+                    clarification: None,
+                    simulation: None,
                     data: None,
                 })
             },
@@ -465,21 +995,68 @@ impl IntentAgent {
         }
     }
     
+    /// The project's offering token, when it has been deployed and reads as
+    /// an ERC-20. A failed read leaves the rest of the answer intact.
+    async fn offering_token(&self, project: &Project) -> Option<TokenInfo> {
+        if project.offering_token.is_zero() {
+            return None;
+        }
+        match self.web3_service.token_info(project.offering_token).await {
+            Ok(token) => Some(token),
+            Err(e) => {
+                println!("⚠️ Could not read offering token {:?}: {}", project.offering_token, e);
+                None
+            }
+        }
+    }
+    
+    async fn execute_get_project_analytics(&self, project_id: &str) -> Result<IntentResult> {
+        println!("📈 Getting project analytics: {}", project_id);
+        
+        let analytics = self.project_analytics(project_id).await
+            .map_err(|e| anyhow!("Failed to get project analytics: {}", e))?;
+        
+        Ok(IntentResult {
+            intent_id: Uuid::new_v4().to_string(),
+            success: true,
+            message: describe_analytics(&analytics, self.web3_service.native_symbol()),
+            ai_message: String::new(),
+            transaction_data: None,
+            transaction_hash: None,
+            clarification: None,
+            simulation: None,
+            data: Some(serde_json::json!({
+                "action": "get_project_analytics",
+                "project": {
+                    "address": analytics.project,
+                    "name": analytics.name,
+                    "symbol": analytics.symbol,
+                },
+                "analytics": analytics,
+                "requires_signing": false,
+            })),
+        })
+    }
+    
     async fn execute_list_projects(&self) -> Result<IntentResult> {
         println!("📋 Listing all projects");
         
         match self.web3_service.get_all_projects().await {
-            Ok(projects) => {
+            Ok(batch) => {
+                self.project_resolver.remember(&batch.projects).await;
                 Ok(IntentResult {
                     intent_id: Uuid::new_v4().to_string(),
                     success: true,
-                    message: format!("Found {} projects", projects.len()),
+                    message: format!("Found {} projects", batch.projects.len()) + &failure_note(&batch.failures),
                     ai_message: String::new(),
                     transaction_data: None,
                     transaction_hash: None,
+                    clarification: None,
+                    simulation: None,
                     data: Some(serde_json::json!({
-                        "projects": projects,
-                        "count": projects.len(),
+                        "projects": project_summaries(&batch.projects),
+                        "count": batch.projects.len(),
+                        "failures": batch.failures,
                         "action": "list_projects",
                         "requires_signing": false,
                     })),
@@ -491,6 +1068,125 @@ impl IntentAgent {
         }
     }
     
+    async fn execute_search_projects(&self, query: &str, cursor: Option<&str>) -> Result<IntentResult> {
+        println!("🔎 Searching projects: {}", query);
+        
+        let addresses = self.web3_service.search_project_addresses(query).await
+            .map_err(|e| anyhow!("Failed to search projects: {}", e))?;
+        let listing = Listing {
+            action: "search_projects",
+            title: format!("Projects matching \"{}\"", query),
+            next_draft: IntentDraft::new("SearchProjects").with_slot("query", serde_json::json!(query)),
+        };
+        self.page_through(listing, addresses, cursor).await
+    }
+    
+    async fn execute_trending_projects(&self, limit: u64, cursor: Option<&str>) -> Result<IntentResult> {
+        println!("🔥 Getting top {} trending projects", limit);
+        
+        let addresses = self.web3_service.trending_project_addresses(limit).await
+            .map_err(|e| anyhow!("Failed to get trending projects: {}", e))?;
+        let listing = Listing {
+            action: "trending_projects",
+            title: "Trending projects".to_string(),
+            next_draft: IntentDraft::new("TrendingProjects").with_slot("limit", serde_json::json!(limit)),
+        };
+        self.page_through(listing, addresses, cursor).await
+    }
+    
+    async fn execute_new_projects(&self, limit: u64, cursor: Option<&str>) -> Result<IntentResult> {
+        println!("🆕 Getting {} newly launched projects", limit);
+        
+        let addresses = self.web3_service.newly_launched_project_addresses(limit).await
+            .map_err(|e| anyhow!("Failed to get new projects: {}", e))?;
+        let listing = Listing {
+            action: "new_projects",
+            title: "Newly launched projects".to_string(),
+            next_draft: IntentDraft::new("NewProjects").with_slot("limit", serde_json::json!(limit)),
+        };
+        self.page_through(listing, addresses, cursor).await
+    }
+    
+    async fn execute_projects_by_status(&self, status: ProjectStatus, page: u64) -> Result<IntentResult> {
+        println!("🏷️ Getting {} projects, page {}", status.name(), page);
+        
+        // The contract pages this listing itself
+        let batch = self.web3_service
+            .get_projects_by_status(status.as_u8(), page, DISCOVERY_PAGE_SIZE as u64)
+            .await
+            .map_err(|e| anyhow!("Failed to get {} projects: {}", status.name(), e))?;
+        self.project_resolver.remember(&batch.projects).await;
+        let has_more = batch.projects.len() + batch.failures.len() == DISCOVERY_PAGE_SIZE;
+        let next_page = has_more.then(|| {
+            IntentDraft::new("ProjectsByStatus")
+                .with_slot("status", serde_json::json!(status))
+                .with_slot("page", serde_json::json!(page + 1))
+        });
+        let offset = (page.saturating_sub(1) as usize) * DISCOVERY_PAGE_SIZE;
+        let title = format!("{} projects", capitalize(status.name()));
+        
+        Ok(IntentResult {
+            intent_id: Uuid::new_v4().to_string(),
+            success: true,
+            message: render_project_page(&title, &batch.projects, offset, None, has_more) + &failure_note(&batch.failures),
+            ai_message: String::new(),
+            transaction_data: None,
+            transaction_hash: None,
+            clarification: None,
+            simulation: None,
+            data: Some(serde_json::json!({
+                "action": "projects_by_status",
+                "status": status,
+                "page": page,
+                "projects": project_summaries(&batch.projects),
+                "count": batch.projects.len(),
+                "failures": batch.failures,
+                "next_cursor": has_more.then(|| (page + 1).to_string()),
+                "next_page": next_page,
+                "requires_signing": false,
+            })),
+        })
+    }
+    
+    /// Fetches details for one page of `addresses`, starting at `cursor`
+    /// (an offset from an earlier page), and describes how to get the next.
+    async fn page_through(&self, listing: Listing, addresses: Vec<Address>, cursor: Option<&str>) -> Result<IntentResult> {
+        let total = addresses.len();
+        let offset = cursor.and_then(|c| c.parse::<usize>().ok()).unwrap_or(0).min(total);
+        let end = (offset + DISCOVERY_PAGE_SIZE).min(total);
+        
+        let batch = self.web3_service.get_projects(&addresses[offset..end]).await;
+        self.project_resolver.remember(&batch.projects).await;
+        let has_more = end < total;
+        let next_cursor = has_more.then(|| end.to_string());
+        let next_page = next_cursor
+            .as_ref()
+            .map(|cursor| listing.next_draft.clone().with_slot("cursor", serde_json::json!(cursor)));
+        
+        Ok(IntentResult {
+            intent_id: Uuid::new_v4().to_string(),
+            success: true,
+            message: render_project_page(&listing.title, &batch.projects, offset, Some(total), has_more) + &failure_note(&batch.failures),
+            ai_message: String::new(),
+            transaction_data: None,
+            transaction_hash: None,
+            clarification: None,
+            simulation: None,
+            data: Some(serde_json::json!({
+                "action": listing.action,
+                "filters": listing.next_draft.slots,
+                "projects": project_summaries(&batch.projects),
+                "count": batch.projects.len(),
+                "failures": batch.failures,
+                "total": total,
+                "cursor": offset.to_string(),
+                "next_cursor": next_cursor,
+                "next_page": next_page,
+                "requires_signing": false,
+            })),
+        })
+    }
+    
     async fn execute_get_user_balance(&self) -> Result<IntentResult> {
         println!("💰 Getting user balance");
         
@@ -502,6 +1198,8 @@ impl IntentAgent {
             ai_message: String::new(),
             transaction_data: None,
             transaction_hash: None,
+            clarification: None,
+            simulation: None,
             data: Some(serde_json::json!({
                 "action": "get_balance",
                 "requires_signing": false,
@@ -510,6 +1208,157 @@ impl IntentAgent {
         })
     }
     
+    async fn execute_my_investments(&self, ctx: &UserContext) -> Result<IntentResult> {
+        let Some(wallet) = &ctx.wallet_address else {
+            return Ok(IntentResult {
+                intent_id: Uuid::new_v4().to_string(),
+                success: false,
+                message: "Connect your wallet to see your investments".to_string(),
+                ai_message: String::new(),
+                transaction_data: None,
+                transaction_hash: None,
+                clarification: None,
+                simulation: None,
+                data: Some(serde_json::json!({
+                    "action": "my_investments",
+                    "requires_wallet": true,
+                    "requires_signing": false,
+                })),
+            });
+        };
+        println!("💼 Getting investments of {}", wallet);
+        
+        let investments = self.investments(wallet).await
+            .map_err(|e| anyhow!("Failed to get investments: {}", e))?;
+        let mut project_ids: Vec<Address> = Vec::new();
+        for investment in &investments {
+            if !project_ids.contains(&investment.project_id) {
+                project_ids.push(investment.project_id);
+            }
+        }
+        let batch = self.web3_service.get_projects(&project_ids).await;
+        self.project_resolver.remember(&batch.projects).await;
+        let mut tokens = Vec::new();
+        for project in &batch.projects {
+            tokens.extend(self.offering_token(project).await);
+        }
+        
+        Ok(IntentResult {
+            intent_id: Uuid::new_v4().to_string(),
+            success: true,
+            message: describe_investments(wallet, &investments, &batch.projects, &tokens, self.web3_service.native_symbol()) + &failure_note(&batch.failures),
+            ai_message: String::new(),
+            transaction_data: None,
+            transaction_hash: None,
+            clarification: None,
+            simulation: None,
+            data: Some(serde_json::json!({
+                "action": "my_investments",
+                "wallet": wallet,
+                "investments": investments,
+                "projects": project_summaries(&batch.projects),
+                "tokens": tokens.iter().map(token_json).collect::<Vec<_>>(),
+                "failures": batch.failures,
+                "requires_signing": false,
+            })),
+        })
+    }
+    
+    /// The connected wallet's balance of a token, given directly or as the
+    /// offering token of a project.
+    async fn execute_get_token_balance(
+        &self,
+        ctx: &UserContext,
+        token: Option<&str>,
+        project_id: Option<&str>,
+    ) -> Result<IntentResult> {
+        let Some(wallet) = &ctx.wallet_address else {
+            return Ok(IntentResult {
+                intent_id: Uuid::new_v4().to_string(),
+                success: false,
+                message: "Connect your wallet to see your token balance".to_string(),
+                ai_message: String::new(),
+                transaction_data: None,
+                transaction_hash: None,
+                clarification: None,
+                simulation: None,
+                data: Some(serde_json::json!({
+                    "action": "get_token_balance",
+                    "requires_wallet": true,
+                    "requires_signing": false,
+                })),
+            });
+        };
+        let owner = Address::from_str(wallet)
+            .map_err(|e| anyhow!("Invalid wallet address {}: {}", wallet, e))?;
+        
+        // A project stands for the token it offers; an address that is no
+        // project is taken to be the token itself
+        let reference = token.or(project_id).ok_or_else(|| anyhow!("No token or project given"))?;
+        let address = Address::from_str(reference)
+            .map_err(|e| anyhow!("Invalid token address {}: {}", reference, e))?;
+        let project = match token {
+            Some(_) => None,
+            None => self.web3_service.get_project(reference).await?,
+        };
+        let token_address = match &project {
+            Some(project) if project.offering_token.is_zero() => {
+                return Ok(IntentResult {
+                    intent_id: Uuid::new_v4().to_string(),
+                    success: false,
+                    message: format!("{} ({}) has no token deployed yet", project.name, project.symbol),
+                    ai_message: String::new(),
+                    transaction_data: None,
+                    transaction_hash: None,
+                    clarification: None,
+                    simulation: None,
+                    data: Some(serde_json::json!({
+                        "action": "get_token_balance",
+                        "project": project_json(project, unix_now()),
+                        "requires_signing": false,
+                    })),
+                });
+            }
+            Some(project) => project.offering_token,
+            None => address,
+        };
+        println!("🪙 Getting balance of token {:?} for {}", token_address, wallet);
+        
+        let info = self.web3_service.token_info(token_address).await
+            .map_err(|e| anyhow!("Failed to read token {:?}: {}", token_address, e))?;
+        let balance = self.web3_service.token_balance(token_address, owner).await
+            .map_err(|e| anyhow!("Failed to get token balance: {}", e))?;
+        let allowance = match self.web3_service.token_allowance(token_address, owner).await {
+            Ok(allowance) => Some(allowance),
+            Err(e) => {
+                println!("⚠️ Could not read allowance of {:?}: {}", token_address, e);
+                None
+            }
+        };
+        let formatted = units::format_units(balance, info.decimals as u32);
+        
+        Ok(IntentResult {
+            intent_id: Uuid::new_v4().to_string(),
+            success: true,
+            message: format!("You hold {} {} ({})", formatted, info.symbol, info.name),
+            ai_message: String::new(),
+            transaction_data: None,
+            transaction_hash: None,
+            clarification: None,
+            simulation: None,
+            data: Some(serde_json::json!({
+                "action": "get_token_balance",
+                "wallet": wallet,
+                "token": token_json(&info),
+                "project": project.as_ref().map(|project| project_json(project, unix_now())),
+                "balance": formatted,
+                "balance_raw": balance.to_string(),
+                "launchpad_allowance": allowance.map(|allowance| allowance.to_string()),
+                "requires_signing": false,
+            })),
+        })
+    }
+    
     async fn execute_get_project_statistics(&self) -> Result<IntentResult> {
         println!("📈 Getting project statistics");
         
@@ -522,12 +1371,15 @@ impl IntentAgent {
                     ai_message: String::new(),
                     transaction_data: None,
                     transaction_hash: None,
+                    clarification: None,
+                    simulation: None,
                     data: Some(serde_json::json!({
                         "statistics": {
                             "total_projects": total,
                             "active_projects": active,
                             "completed_projects": completed,
-                            "total_raised": total_raised,
+                            "total_raised": total_raised.to_string(),
+                            "total_raised_eth": units::format_ether(total_raised),
                             "total_investors": investors,
                         },
                         "action": "get_statistics",
@@ -541,7 +1393,7 @@ impl IntentAgent {
         }
     }
     
-    async fn generate_ai_response(&self, intent_result: &IntentResult) -> Result<String> {
+    fn build_response_prompt(&self, intent_result: &IntentResult) -> Result<String> {
         let mut context = format!(
             "User intent result:\n\
              Success: {}\n\
@@ -571,20 +1423,86 @@ impl IntentAgent {
             context += "\nNo transaction required for this action. Please provide a helpful response to the user about the information they requested.\n";
         }
         
+        if let Some(simulation) = &intent_result.simulation {
+            let reason = simulation.reason.as_deref().unwrap_or("none");
+            context += &match simulation.verdict {
+                SimulationVerdict::Succeeds => "\nSimulation: the transaction succeeds against the latest block.\n".to_string(),
+                SimulationVerdict::Reverts => format!(
+                    "\nSimulation: the transaction would revert. Reason: {}\n\
+                     Explain in plain words why it would fail, and do not ask the user to sign anything.\n",
+                    reason
+                ),
+                SimulationVerdict::Unknown => format!(
+                    "\nSimulation: could not be run ({}). Mention that the transaction has not been checked.\n",
+                    reason
+                ),
+            };
+        }
+        
         if !intent_result.success {
             context += "\nThe operation failed. Please provide a helpful error message and suggest what the user can do next.\n";
         }
         
-        let prompt = format!(
+        Ok(format!(
             "You are Teemah AI, a helpful Web3 assistant. Generate a friendly, informative response based on this context:\n\n{}\n\nResponse should be in markdown format, be concise, and helpful.",
             context
-        );
+        ))
+    }
+    
+    fn build_response_messages(&self, intent_result: &IntentResult, history: &[Message]) -> Result<Vec<Message>> {
+        let prompt = self.build_response_prompt(intent_result)?;
+        let mut messages = vec![Message::system(DEFAULT_SYSTEM_PROMPT)];
+        messages.extend_from_slice(history);
+        messages.push(Message::user(&prompt));
+        Ok(messages)
+    }
+    
+    async fn generate_ai_response(&self, intent_result: &IntentResult, history: &[Message]) -> Result<String> {
+        let messages = self.build_response_messages(intent_result, history)?;
+        match self.llm_client.chat(messages).await {
+            Ok(message) => Ok(message),
+            Err(e) => {
+                println!("⚠️ LLM unavailable ({}), using templated response", e);
+                Ok(fallback_ai_message(intent_result))
+            }
+        }
+    }
+    
+    async fn stream_ai_response(
+        &self,
+        intent_result: &IntentResult,
+        history: &[Message],
+        events: &UnboundedSender<IntentEvent>,
+    ) -> Result<String> {
+        let messages = self.build_response_messages(intent_result, history)?;
+        let mut tokens = match self.llm_client.chat_stream(messages).await {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                println!("⚠️ LLM unavailable ({}), using templated response", e);
+                let message = fallback_ai_message(intent_result);
+                let _ = events.unbounded_send(IntentEvent::Token { text: message.clone() });
+                return Ok(message);
+            }
+        };
         
-        self.llm_client.generate_response(&prompt).await
+        let mut ai_message = String::new();
+        while let Some(token) = tokens.next().await {
+            let token = token?;
+            ai_message.push_str(&token);
+            let _ = events.unbounded_send(IntentEvent::Token { text: token });
+        }
+        Ok(ai_message)
+    }
+    
+    fn track(&self, update: std::result::Result<IntentRecord, IntentStoreError>) {
+        match update {
+            Ok(record) => println!("🗂️ Intent {} is now {:?}", record.intent_id, record.state),
+            Err(e) => println!("⚠️ Intent store: {}", e),
+        }
     }
     
-    pub async fn get_active_intents(&self) -> Vec<IntentResult> {
-        self.active_intents.values().cloned().collect()
+    pub fn llm_provider_name(&self) -> &str {
+        self.llm_client.name()
     }
     
     // Note: Commented out since users sign their own transactions
@@ -593,3 +1511,387 @@ impl IntentAgent {
     //     Ok(())
     // }
 }
+
+/// Deterministic reply used when no LLM is reachable.
+fn fallback_ai_message(intent_result: &IntentResult) -> String {
+    let mut message = if intent_result.success {
+        format!("✅ {}", intent_result.message)
+    } else {
+        format!("❌ {}", intent_result.message)
+    };
+    
+    if let Some(tx_data) = &intent_result.transaction_data {
+        message += &format!(
+            "\n\n📝 {}\n\nPlease review and sign the transaction in your wallet.",
+            tx_data.description
+        );
+        if let Some(Simulation { verdict: SimulationVerdict::Unknown, reason: Some(reason), .. }) = &intent_result.simulation {
+            message += &format!("\n\n⚠️ This transaction could not be checked beforehand: {}", reason);
+        }
+    }
+    message
+}
+
+/// A discovery listing being paged: what it is called and the draft that
+/// re-runs it, to which the next page's cursor is added.
+struct Listing {
+    action: &'static str,
+    title: String,
+    next_draft: IntentDraft,
+}
+
+fn project_summaries(projects: &[Project]) -> Vec<serde_json::Value> {
+    let now = unix_now();
+    projects
+        .iter()
+        .map(|project| serde_json::json!({
+            "address": project.address,
+            "name": project.name,
+            "symbol": project.symbol,
+            "status": project.status,
+            "total_raised": project.total_raised.to_string(),
+            "hard_cap": project.hard_cap.to_string(),
+            "progress_percent": project.progress_percent(),
+            "time_remaining_secs": project.time_remaining(now),
+        }))
+        .collect()
+}
+
+/// One line per project. Rows are numbered within the page, matching the
+/// list the conversation remembers for follow-ups like "the third one".
+fn render_project_page(title: &str, projects: &[Project], offset: usize, total: Option<usize>, has_more: bool) -> String {
+    if projects.is_empty() {
+        return format!("{}: no projects found", title);
+    }
+    let range = format!("{}-{}", offset + 1, offset + projects.len());
+    let mut message = match total {
+        Some(total) => format!("{} ({} of {}):", title, range, total),
+        None => format!("{} ({}):", title, range),
+    };
+    for (i, project) in projects.iter().enumerate() {
+        message += &format!(
+            "\n{}. {} ({}) — {:?} · {}, {}% raised",
+            i + 1, project.name, project.symbol, project.address, project.status.name(), project.progress_percent()
+        );
+    }
+    if has_more {
+        message += "\nSay \"more\" to see the next page.";
+    }
+    message
+}
+
+/// Names the projects a listing could not load, so gaps are not silent.
+fn failure_note(failures: &[ProjectFetchError]) -> String {
+    if failures.is_empty() {
+        return String::new();
+    }
+    let mut note = format!("\n⚠️ {} project(s) could not be loaded:", failures.len());
+    for failure in failures {
+        note += &format!("\n- {:?}: {}", failure.address, failure.reason);
+    }
+    note
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Every `getProject` figure plus the derived metrics. Amounts are wei as
+/// decimal strings, times are unix seconds.
+fn project_json(project: &Project, now: u64) -> serde_json::Value {
+    serde_json::json!({
+        "address": project.address,
+        "creator": project.creator,
+        "offering_token": project.offering_token,
+        "name": project.name,
+        "symbol": project.symbol,
+        "status": project.status,
+        "soft_cap": project.soft_cap.to_string(),
+        "hard_cap": project.hard_cap.to_string(),
+        "total_raised": project.total_raised.to_string(),
+        "start_time": project.start_time.to_string(),
+        "end_time": project.end_time.to_string(),
+        "metrics": project.metrics(now),
+    })
+}
+
+fn token_json(token: &TokenInfo) -> serde_json::Value {
+    serde_json::json!({
+        "address": token.address,
+        "name": token.name,
+        "symbol": token.symbol,
+        "decimals": token.decimals,
+        "total_supply": units::format_units(token.total_supply, token.decimals as u32),
+        "total_supply_raw": token.total_supply.to_string(),
+    })
+}
+
+/// Marketing info with amounts both in wei and in ether, like `project_json`.
+pub fn marketing_json(marketing: &MarketingInfo) -> serde_json::Value {
+    serde_json::json!({
+        "allocation": marketing.allocation.to_string(),
+        "allocation_eth": units::format_ether(marketing.allocation),
+        "wallet": marketing.wallet,
+        "released": marketing.released.to_string(),
+        "released_eth": units::format_ether(marketing.released),
+        "is_active": marketing.is_active,
+        "last_updated": marketing.last_updated.to_string(),
+    })
+}
+
+fn describe_marketing(marketing: &MarketingInfo, symbol: &str) -> String {
+    if marketing.allocation.is_zero() {
+        return "It has no marketing allocation.".to_string();
+    }
+    format!(
+        "Marketing: {} of {} {} released to {:?}, {}.",
+        units::format_ether(marketing.released),
+        units::format_ether(marketing.allocation),
+        symbol,
+        marketing.wallet,
+        if marketing.is_active { "active" } else { "inactive" },
+    )
+}
+
+fn describe_token(token: &TokenInfo) -> String {
+    format!(
+        "Its token is {} ({}) at {:?}, with a supply of {}.",
+        token.name,
+        token.symbol,
+        token.address,
+        units::format_units(token.total_supply, token.decimals as u32),
+    )
+}
+
+fn describe_project(project: &Project, now: u64, symbol: &str) -> String {
+    let metrics = project.metrics(now);
+    let mut message = format!(
+        "{} ({}) is {}: {} of {} {symbol} raised ({}%), soft cap {} {symbol}",
+        project.name,
+        project.symbol,
+        project.status.name(),
+        units::format_ether(project.total_raised),
+        units::format_ether(project.hard_cap),
+        metrics.progress_percent,
+        units::format_ether(project.soft_cap),
+    );
+    message += if metrics.hard_cap_reached {
+        " — hard cap reached"
+    } else if metrics.soft_cap_reached {
+        " — soft cap reached"
+    } else {
+        ""
+    };
+    message += &match metrics.phase {
+        FundingPhase::Upcoming => format!(". The sale has not started; it ends in {}.", format_duration(metrics.time_remaining_secs)),
+        FundingPhase::Live => format!(". {} left in the sale.", format_duration(metrics.time_remaining_secs)),
+        FundingPhase::Succeeded | FundingPhase::Failed => ". The sale has ended.".to_string(),
+    };
+    message
+}
+
+fn describe_analytics(analytics: &ProjectAnalytics, symbol: &str) -> String {
+    let wei = |amount: &str| units::format_ether(U256::from_dec_str(amount).unwrap_or_default());
+    let mut message = format!(
+        "{} ({}): {}% of the hard cap raised from {} investment(s) by {} investor(s), averaging {} {symbol} each. Raising {} {symbol} per hour.",
+        analytics.name,
+        analytics.symbol,
+        analytics.metrics.progress_percent,
+        analytics.investment_count,
+        analytics.investor_count,
+        wei(&analytics.average_ticket),
+        wei(&analytics.velocity_wei_per_hour),
+    );
+    if let Some(at) = analytics.projected_completion {
+        let eta = format_duration(at.saturating_sub(unix_now()));
+        message += &match analytics.fills_before_end {
+            Some(false) => format!(" At this pace the hard cap fills in {}, after the sale ends.", eta),
+            _ => format!(" At this pace the hard cap fills in {}.", eta),
+        };
+    }
+    message
+}
+
+/// One line per project invested in, with contributions to the same project
+/// added up. Token amounts use the decimals of the project's token when it
+/// is among `tokens`.
+fn describe_investments(
+    wallet: &str,
+    investments: &[Investment],
+    projects: &[Project],
+    tokens: &[TokenInfo],
+    symbol: &str,
+) -> String {
+    if investments.is_empty() {
+        return format!("No investments found for {}.", wallet);
+    }
+    let total = investments.iter().fold(U256::zero(), |sum, i| sum.saturating_add(i.amount));
+    let mut lines = Vec::new();
+    let mut seen: Vec<Address> = Vec::new();
+    for investment in investments {
+        if seen.contains(&investment.project_id) {
+            continue;
+        }
+        seen.push(investment.project_id);
+        let positions: Vec<&Investment> = investments.iter().filter(|i| i.project_id == investment.project_id).collect();
+        let amount = positions.iter().fold(U256::zero(), |sum, i| sum.saturating_add(i.amount));
+        let tokens_bought = positions.iter().fold(U256::zero(), |sum, i| sum.saturating_add(i.token_amount));
+        let project = projects.iter().find(|p| p.address == investment.project_id);
+        let label = match project {
+            Some(project) => format!("{} ({})", project.name, project.symbol),
+            None => format!("{:?}", investment.project_id),
+        };
+        let token = project.and_then(|project| tokens.iter().find(|t| t.address == project.offering_token));
+        let bought = match token {
+            Some(token) => format!("{} {}", units::format_units(tokens_bought, token.decimals as u32), token.symbol),
+            // Unreadable tokens are shown with the ERC-20 default of 18 decimals
+            None => format!("{} tokens", units::format_ether(tokens_bought)),
+        };
+        let state = if positions.iter().any(|i| i.refunded) {
+            " · refunded"
+        } else if positions.iter().any(|i| i.tokens_claimed) {
+            " · tokens claimed"
+        } else {
+            ""
+        };
+        lines.push(format!("• {}: {} {symbol} for {}{}", label, units::format_ether(amount), bought, state));
+    }
+    format!(
+        "💼 {} investment(s) in {} project(s), {} {symbol} in total:\n{}",
+        investments.len(),
+        seen.len(),
+        units::format_ether(total),
+        lines.join("\n")
+    )
+}
+
+/// "2d 3h", "5h 10m", "42m"; minutes are dropped once days are shown.
+fn format_duration(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes),
+        (0, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+/// A refused refund or completion: nothing to sign, with the reason and the
+/// figures it was based on.
+fn ineligible_result(action: &str, project_id: &str, reason: &str, project: &Project, now: u64) -> IntentResult {
+    IntentResult {
+        intent_id: Uuid::new_v4().to_string(),
+        success: false,
+        message: reason.to_string(),
+        ai_message: String::new(),
+        transaction_data: None,
+        transaction_hash: None,
+        clarification: None,
+        simulation: None,
+        data: Some(serde_json::json!({
+            "action": action,
+            "project_id": project_id,
+            "eligible": false,
+            "reason": reason,
+            "project": project_json(project, now),
+        })),
+    }
+}
+
+fn clarification_result(intent_id: &str, clarification: &Clarification) -> IntentResult {
+    IntentResult {
+        intent_id: intent_id.to_string(),
+        success: false,
+        message: format!("More information needed for {}", clarification.intent),
+        ai_message: format!("🤔 {}", clarification.question),
+        transaction_data: None,
+        transaction_hash: None,
+        data: Some(serde_json::json!({
+            "action": "clarify",
+            "intent": clarification.intent,
+            "question": clarification.question,
+            "missing_fields": clarification.missing_fields,
+            "invalid_fields": clarification.invalid_fields,
+            "filled": clarification.draft.slots,
+            "choices": clarification.choices,
+            // Lets follow-ups like "the second one" refer to the offered projects
+            "projects": clarification.choices
+                .iter()
+                .map(|choice| serde_json::json!({ "address": choice.value, "name": choice.label }))
+                .collect::<Vec<_>>(),
+            "requires_signing": false,
+        })),
+        clarification: Some(clarification.clone()),
+        simulation: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intent_store::IntentState;
+    use crate::llm::MockLlmClient;
+
+    const LAUNCHPAD: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    /// An agent whose LLM replays `responses` and whose RPC endpoint refuses
+    /// connections, so only offline paths can succeed.
+    fn offline_agent(responses: &[&str]) -> IntentAgent {
+        let llm = MockLlmClient::new(responses.iter().map(|r| r.to_string()).collect());
+        let web3_service = Web3Service::new_without_signer("http://127.0.0.1:9", LAUNCHPAD, 97).unwrap();
+        IntentAgent::new("test", Arc::new(llm), web3_service, ConversationStore::new(), IntentStore::new())
+    }
+
+    #[tokio::test]
+    async fn runs_a_tool_call_through_the_pipeline() {
+        let agent = offline_agent(&[
+            r#"{"name": "GetUserBalance", "arguments": {}}"#,
+            "Connect your wallet and I'll show your balance.",
+        ]);
+        let ctx = UserContext::new(1, Some("mock-pipeline".to_string()));
+
+        let result = agent.process_user_intent(&ctx, "how much do I have in my wallet").await.unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.ai_message, "Connect your wallet and I'll show your balance.");
+        assert_eq!(result.data.as_ref().unwrap()["action"], "get_balance");
+        let record = agent.intents.get(&result.intent_id).await.unwrap();
+        assert!(matches!(record.intent, Some(Intent::GetUserBalance)));
+        let conversation = agent.conversations.get("mock-pipeline").await.unwrap();
+        assert_eq!(conversation.turns.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn asks_for_a_missing_slot_and_keeps_the_draft() {
+        let agent = offline_agent(&[&format!(r#"{{"name": "Invest", "arguments": {{"project_id": "{}"}}}}"#, LAUNCHPAD)]);
+        let ctx = UserContext::new(1, Some("mock-clarify".to_string()));
+
+        let result = agent.process_user_intent(&ctx, "put some money into that launchpad project").await.unwrap();
+
+        assert!(!result.success);
+        let clarification = result.clarification.unwrap();
+        assert_eq!(clarification.missing_fields, vec!["amount"]);
+        assert_eq!(clarification.draft.slots["project_id"], LAUNCHPAD);
+        let conversation = agent.conversations.get("mock-clarify").await.unwrap();
+        assert_eq!(conversation.pending.unwrap().intent, "Invest");
+        assert_eq!(conversation.pending_intent_id.as_deref(), Some(result.intent_id.as_str()));
+
+        // The answer settles the question, whether or not the follow-up can run offline
+        let answer = agent.process_user_intent(&ctx, "0.5 BNB").await;
+        let question = agent.intents.get(&result.intent_id).await.unwrap();
+        assert_eq!(question.state, IntentState::Superseded);
+        if let Ok(answer) = answer {
+            assert_eq!(question.superseded_by, Some(answer.intent_id));
+        }
+    }
+}
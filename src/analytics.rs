@@ -0,0 +1,256 @@
+use crate::launchpad_services::{InvestedFilter, LaunchpadEvent, Project, ProjectMetrics, ProjectStatus};
+use crate::web3::Web3Service;
+use anyhow::{anyhow, Result};
+use ethers::contract::EthEvent;
+use ethers::providers::Middleware;
+use ethers::types::{Address, Filter, H256, U256};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Blocks per `eth_getLogs` request; public RPCs reject much wider ranges.
+const LOG_CHUNK_BLOCKS: u64 = 5_000;
+/// Upper bound on chunks scanned back from the head (about 35 days on BSC).
+const MAX_SCANNED_CHUNKS: u64 = 200;
+const SECONDS_PER_HOUR: u64 = 3_600;
+
+/// Fundraising analytics for one project: the raise figures from `getProject`
+/// plus what its `Invested` events say about who invests and how fast.
+/// Amounts are wei as decimal strings, times are unix seconds.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectAnalytics {
+    pub project: Address,
+    pub name: String,
+    pub symbol: String,
+    pub status: ProjectStatus,
+    pub total_raised: String,
+    pub soft_cap: String,
+    pub hard_cap: String,
+    pub metrics: ProjectMetrics,
+    pub investment_count: usize,
+    pub investor_count: usize,
+    pub average_ticket: String,
+    pub velocity_wei_per_hour: String,
+    /// When the hard cap fills at the current velocity, while the raise is live.
+    pub projected_completion: Option<u64>,
+    /// Whether that happens before the sale ends.
+    pub fills_before_end: Option<bool>,
+    /// Blocks whose `Invested` events were counted.
+    pub from_block: u64,
+    pub to_block: u64,
+}
+
+struct Investment {
+    investor: Address,
+    amount: U256,
+    timestamp: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectAnalyzer {
+    web3_service: Web3Service,
+}
+
+impl ProjectAnalyzer {
+    pub fn new(web3_service: Web3Service) -> Self {
+        Self { web3_service }
+    }
+
+    pub async fn analyze(&self, project_id: &str, now: u64) -> Result<ProjectAnalytics> {
+        let project = self
+            .web3_service
+            .get_project(project_id)
+            .await?
+            .ok_or_else(|| anyhow!("Project not found: {}", project_id))?;
+
+        let (investments, from_block, to_block) = self.investments(&project).await?;
+        println!(
+            "📈 {} ({}): {} investment(s) in blocks {}-{}",
+            project.name, project.symbol, investments.len(), from_block, to_block
+        );
+
+        let investors: HashSet<Address> = investments.iter().map(|i| i.investor).collect();
+        let observed = investments.iter().fold(U256::zero(), |sum, i| sum.saturating_add(i.amount));
+        let average_ticket = average_ticket(&investments, observed);
+        let velocity = velocity(&project, &investments, observed, now);
+        let projected_completion = projected_completion(&project, velocity, now);
+
+        Ok(ProjectAnalytics {
+            project: project.address,
+            name: project.name.clone(),
+            symbol: project.symbol.clone(),
+            status: project.status,
+            total_raised: project.total_raised.to_string(),
+            soft_cap: project.soft_cap.to_string(),
+            hard_cap: project.hard_cap.to_string(),
+            metrics: project.metrics(now),
+            investment_count: investments.len(),
+            investor_count: investors.len(),
+            average_ticket: average_ticket.to_string(),
+            velocity_wei_per_hour: velocity.to_string(),
+            projected_completion,
+            fills_before_end: projected_completion
+                .map(|at| project.end_time.is_zero() || U256::from(at) <= project.end_time),
+            from_block,
+            to_block,
+        })
+    }
+
+    /// `Invested` events for the project, scanning back from the head in
+    /// chunks until the chunk starts before the sale opened.
+    async fn investments(&self, project: &Project) -> Result<(Vec<Investment>, u64, u64)> {
+        let provider = self.web3_service.provider();
+        let head = provider.get_block_number().await?.as_u64();
+        let filter = Filter::new()
+            .address(self.web3_service.launchpad_address())
+            .topic0(InvestedFilter::signature())
+            .topic1(H256::from(project.address));
+
+        let mut investments = Vec::new();
+        let mut to = head;
+        let mut from = head;
+        for _ in 0..MAX_SCANNED_CHUNKS {
+            from = to.saturating_sub(LOG_CHUNK_BLOCKS - 1);
+            let logs = provider.get_logs(&filter.clone().from_block(from).to_block(to)).await?;
+            for event in self.web3_service.decode_launchpad_events(&logs) {
+                if let LaunchpadEvent::Invested { investor, amount, timestamp, .. } = event {
+                    investments.push(Investment { investor, amount, timestamp: timestamp.low_u64() });
+                }
+            }
+
+            if from == 0 {
+                break;
+            }
+            let opened_before = match provider.get_block(from).await? {
+                Some(block) => block.timestamp < project.start_time,
+                None => false,
+            };
+            if opened_before {
+                break;
+            }
+            to = from - 1;
+        }
+
+        Ok((investments, from, head))
+    }
+}
+
+fn average_ticket(investments: &[Investment], observed: U256) -> U256 {
+    if investments.is_empty() {
+        U256::zero()
+    } else {
+        observed / U256::from(investments.len())
+    }
+}
+
+/// Wei per hour over the sale so far: from the start time (or the first
+/// investment when none is set) until now or the end of the sale.
+fn velocity(project: &Project, investments: &[Investment], observed: U256, now: u64) -> U256 {
+    let first_investment = investments.iter().map(|i| i.timestamp).min();
+    let start = match project.start_time.low_u64() {
+        0 => match first_investment {
+            Some(at) => at,
+            None => return U256::zero(),
+        },
+        start => start,
+    };
+    let end = match project.end_time.low_u64() {
+        0 => now,
+        end => end.min(now),
+    };
+    let elapsed = end.saturating_sub(start);
+    if elapsed == 0 {
+        return U256::zero();
+    }
+    observed.saturating_mul(U256::from(SECONDS_PER_HOUR)) / U256::from(elapsed)
+}
+
+fn projected_completion(project: &Project, velocity: U256, now: u64) -> Option<u64> {
+    let live = project.end_time.is_zero() || U256::from(now) < project.end_time;
+    if !live || velocity.is_zero() || project.hard_cap_reached() {
+        return None;
+    }
+    let remaining = project.hard_cap - project.total_raised;
+    let seconds = remaining.saturating_mul(U256::from(SECONDS_PER_HOUR)) / velocity;
+    Some(now.saturating_add(seconds.min(U256::from(u64::MAX)).as_u64()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+    const ETHER: u64 = 1_000_000_000_000_000_000;
+
+    /// A live raise with a 100 ETH hard cap that opened `open_for` seconds ago.
+    fn project(total_raised: u64, open_for: u64) -> Project {
+        Project {
+            address: Address::repeat_byte(0x11),
+            creator: Address::repeat_byte(0x22),
+            offering_token: Address::repeat_byte(0x33),
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            soft_cap: U256::from(10) * U256::from(ETHER),
+            hard_cap: U256::from(100) * U256::from(ETHER),
+            total_raised: U256::from(total_raised) * U256::from(ETHER),
+            start_time: U256::from(NOW - open_for),
+            end_time: U256::from(NOW + 30 * 24 * SECONDS_PER_HOUR),
+            status: ProjectStatus::Active,
+        }
+    }
+
+    fn investment(investor: u8, ether: u64, timestamp: u64) -> Investment {
+        Investment {
+            investor: Address::repeat_byte(investor),
+            amount: U256::from(ether) * U256::from(ETHER),
+            timestamp,
+        }
+    }
+
+    fn total(investments: &[Investment]) -> U256 {
+        investments.iter().fold(U256::zero(), |sum, i| sum + i.amount)
+    }
+
+    #[test]
+    fn no_investors_means_no_ticket_and_no_velocity() {
+        let project = project(0, 2 * SECONDS_PER_HOUR);
+        assert_eq!(average_ticket(&[], U256::zero()), U256::zero());
+        assert_eq!(velocity(&project, &[], U256::zero(), NOW), U256::zero());
+        assert_eq!(projected_completion(&project, U256::zero(), NOW), None);
+    }
+
+    #[test]
+    fn velocity_and_ticket_over_several_investments() {
+        let project = project(30, 10 * SECONDS_PER_HOUR);
+        let investments = [investment(1, 10, NOW - 9_000), investment(2, 15, NOW - 5_000), investment(1, 5, NOW - 100)];
+        let observed = total(&investments);
+
+        assert_eq!(average_ticket(&investments, observed), U256::from(10) * U256::from(ETHER));
+        // 30 ETH over the 10 hours since the sale opened
+        let velocity = velocity(&project, &investments, observed, NOW);
+        assert_eq!(velocity, U256::from(3) * U256::from(ETHER));
+        // 70 ETH to go at 3 ETH an hour
+        let hours_left = U256::from(70) * U256::from(SECONDS_PER_HOUR) / U256::from(3);
+        assert_eq!(projected_completion(&project, velocity, NOW), Some(NOW + hours_left.as_u64()));
+    }
+
+    #[test]
+    fn velocity_counts_from_the_first_investment_without_a_start_time() {
+        let mut project = project(4, 0);
+        project.start_time = U256::zero();
+        let investments = [investment(1, 4, NOW - 2 * SECONDS_PER_HOUR)];
+        assert_eq!(velocity(&project, &investments, total(&investments), NOW), U256::from(2) * U256::from(ETHER));
+    }
+
+    #[test]
+    fn zero_elapsed_time_does_not_divide_by_zero() {
+        let project = project(5, 0);
+        let investments = [investment(1, 5, NOW)];
+        assert_eq!(velocity(&project, &investments, total(&investments), NOW), U256::zero());
+    }
+
+    #[test]
+    fn no_projection_once_the_hard_cap_is_reached() {
+        let project = project(100, 10 * SECONDS_PER_HOUR);
+        assert_eq!(projected_completion(&project, U256::from(ETHER), NOW), None);
+    }
+}
@@ -0,0 +1,314 @@
+use crate::gas::{FeeModel, DEFAULT_GAS_LIMIT_BUFFER_PERCENT};
+use crate::launchpad_services::DEFAULT_MULTICALL_ADDRESS;
+use crate::tx_watcher::DEFAULT_CONFIRMATIONS;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+/// Registry file read at startup when `TEEMAH_CHAINS` names no other.
+pub const DEFAULT_CHAINS_PATH: &str = "chains.json";
+const CHAINS_PATH_VAR: &str = "TEEMAH_CHAINS";
+
+/// Everything the backend needs to serve one chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainConfig {
+    pub chain_id: u64,
+    pub name: String,
+    pub native_symbol: String,
+    /// In order of preference.
+    pub rpc_urls: Vec<String>,
+    /// WebSocket endpoints for event subscriptions. Without any, new blocks
+    /// and events are polled over HTTP.
+    #[serde(default)]
+    pub ws_urls: Vec<String>,
+    /// `MechaLaunchpad` deployment. Chains without one are known but not served.
+    #[serde(default)]
+    pub launchpad_address: Option<String>,
+    #[serde(default = "default_multicall_address")]
+    pub multicall_address: String,
+    /// Base URL of the block explorer, without a trailing slash.
+    pub explorer_url: String,
+    /// Endpoints that must return the same answer for reads a transaction
+    /// is prepared on, such as `getProject` before an investment. 1 reads
+    /// from whichever endpoint answers first.
+    #[serde(default = "default_rpc_quorum")]
+    pub rpc_quorum: usize,
+    /// Blocks a transaction must be buried under before it counts as final.
+    #[serde(default = "default_confirmations")]
+    pub confirmations: u64,
+    /// Block the launchpad was deployed in; event indexing starts there.
+    #[serde(default)]
+    pub deployment_block: u64,
+    /// How transactions are priced. Detected from the latest block when unset.
+    #[serde(default)]
+    pub fee_model: Option<FeeModel>,
+    /// Headroom added to gas estimates, in percent.
+    #[serde(default = "default_gas_limit_buffer_percent")]
+    pub gas_limit_buffer_percent: u64,
+}
+
+fn default_multicall_address() -> String {
+    DEFAULT_MULTICALL_ADDRESS.to_string()
+}
+
+fn default_rpc_quorum() -> usize {
+    1
+}
+
+fn default_confirmations() -> u64 {
+    DEFAULT_CONFIRMATIONS
+}
+
+fn default_gas_limit_buffer_percent() -> u64 {
+    DEFAULT_GAS_LIMIT_BUFFER_PERCENT
+}
+
+impl ChainConfig {
+    /// The preferred RPC endpoint.
+    pub fn rpc_url(&self) -> Result<&str> {
+        self.rpc_urls
+            .first()
+            .map(String::as_str)
+            .ok_or_else(|| anyhow!("No RPC URL configured for {} ({})", self.name, self.chain_id))
+    }
+
+    pub fn is_deployed(&self) -> bool {
+        self.launchpad_address.is_some()
+    }
+
+    pub fn explorer_tx_url(&self, tx_hash: &str) -> String {
+        format!("{}/tx/{}", self.explorer_url, tx_hash)
+    }
+}
+
+/// A chain id the registry has no entry for.
+#[derive(Debug, Clone)]
+pub struct UnsupportedChain {
+    pub chain_id: u64,
+    pub supported: Vec<u64>,
+}
+
+impl fmt::Display for UnsupportedChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let supported: Vec<String> = self.supported.iter().map(u64::to_string).collect();
+        write!(f, "chain {} is not supported (supported: {})", self.chain_id, supported.join(", "))
+    }
+}
+
+impl std::error::Error for UnsupportedChain {}
+
+/// The registry as written in the config file:
+///
+/// ```json
+/// { "default_chain_id": 97, "chains": [{ "chain_id": 97, "name": "BSC Testnet", ... }] }
+/// ```
+#[derive(Debug, Deserialize)]
+struct RegistryFile {
+    default_chain_id: Option<u64>,
+    chains: Vec<ChainConfig>,
+}
+
+/// Every chain the backend knows, and the one used when a request names none.
+#[derive(Debug, Clone)]
+pub struct ChainRegistry {
+    chains: BTreeMap<u64, ChainConfig>,
+    default_chain_id: u64,
+}
+
+impl ChainRegistry {
+    /// BSC testnet, where the launchpad is deployed, and BSC mainnet.
+    pub fn builtin() -> Self {
+        let chains = vec![
+            ChainConfig {
+                chain_id: 97,
+                name: "BSC Testnet".to_string(),
+                native_symbol: "tBNB".to_string(),
+                rpc_urls: vec![
+                    "https://data-seed-prebsc-1-s1.binance.org:8545".to_string(),
+                    "https://data-seed-prebsc-2-s1.binance.org:8545".to_string(),
+                ],
+                ws_urls: Vec::new(),
+                launchpad_address: Some("0xE7392b8ee167980602d38225674bB377De5Fe287".to_string()),
+                multicall_address: default_multicall_address(),
+                explorer_url: "https://testnet.bscscan.com".to_string(),
+                rpc_quorum: default_rpc_quorum(),
+                confirmations: DEFAULT_CONFIRMATIONS,
+                deployment_block: 0,
+                // BSC keeps its base fee at zero; validators order by gas price
+                fee_model: Some(FeeModel::Legacy),
+                gas_limit_buffer_percent: DEFAULT_GAS_LIMIT_BUFFER_PERCENT,
+            },
+            ChainConfig {
+                chain_id: 56,
+                name: "BNB Smart Chain".to_string(),
+                native_symbol: "BNB".to_string(),
+                rpc_urls: vec!["https://bsc-dataseed.bnbchain.org".to_string()],
+                ws_urls: Vec::new(),
+                launchpad_address: None,
+                multicall_address: default_multicall_address(),
+                explorer_url: "https://bscscan.com".to_string(),
+                rpc_quorum: default_rpc_quorum(),
+                confirmations: DEFAULT_CONFIRMATIONS,
+                deployment_block: 0,
+                fee_model: Some(FeeModel::Legacy),
+                gas_limit_buffer_percent: DEFAULT_GAS_LIMIT_BUFFER_PERCENT,
+            },
+        ];
+        Self::new(chains, Some(97)).expect("built-in chain registry is valid")
+    }
+
+    pub fn new(chains: Vec<ChainConfig>, default_chain_id: Option<u64>) -> Result<Self> {
+        let mut registry = BTreeMap::new();
+        for chain in chains {
+            if chain.rpc_urls.is_empty() {
+                return Err(anyhow!("{} ({}) has no RPC URLs", chain.name, chain.chain_id));
+            }
+            if chain.rpc_quorum > chain.rpc_urls.len() {
+                return Err(anyhow!(
+                    "{} ({}) needs {} RPC endpoints to agree but lists {}",
+                    chain.name, chain.chain_id, chain.rpc_quorum, chain.rpc_urls.len()
+                ));
+            }
+            let chain_id = chain.chain_id;
+            if registry.insert(chain_id, chain).is_some() {
+                return Err(anyhow!("chain {} is listed more than once", chain_id));
+            }
+        }
+
+        // Without an explicit default, the first chain with a launchpad
+        let default_chain_id = default_chain_id
+            .or_else(|| registry.values().find(|c| c.is_deployed()).map(|c| c.chain_id))
+            .or_else(|| registry.keys().next().copied())
+            .ok_or_else(|| anyhow!("the chain registry is empty"))?;
+        if !registry.contains_key(&default_chain_id) {
+            return Err(anyhow!("default chain {} is not in the registry", default_chain_id));
+        }
+        Ok(Self { chains: registry, default_chain_id })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("reading chain registry {}", path.display()))?;
+        let file: RegistryFile = serde_json::from_str(&contents)
+            .with_context(|| format!("parsing chain registry {}", path.display()))?;
+        Self::new(file.chains, file.default_chain_id)
+    }
+
+    /// The file named by `TEEMAH_CHAINS`, else `chains.json` when present,
+    /// else the built-in chains.
+    pub fn from_env() -> Result<Self> {
+        if let Ok(path) = std::env::var(CHAINS_PATH_VAR) {
+            return Self::load(path);
+        }
+        if Path::new(DEFAULT_CHAINS_PATH).exists() {
+            return Self::load(DEFAULT_CHAINS_PATH);
+        }
+        Ok(Self::builtin())
+    }
+
+    pub fn get(&self, chain_id: u64) -> Result<&ChainConfig, UnsupportedChain> {
+        self.chains.get(&chain_id).ok_or_else(|| UnsupportedChain {
+            chain_id,
+            supported: self.chains.keys().copied().collect(),
+        })
+    }
+
+    pub fn default_chain_id(&self) -> u64 {
+        self.default_chain_id
+    }
+
+    pub fn chains(&self) -> impl Iterator<Item = &ChainConfig> {
+        self.chains.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = r#"{
+        "chains": [
+            {
+                "chain_id": 1,
+                "name": "Ethereum",
+                "native_symbol": "ETH",
+                "rpc_urls": ["https://eth.example"],
+                "explorer_url": "https://etherscan.io"
+            },
+            {
+                "chain_id": 11155111,
+                "name": "Sepolia",
+                "native_symbol": "SepoliaETH",
+                "rpc_urls": ["https://sepolia-1.example", "https://sepolia-2.example"],
+                "launchpad_address": "0x0000000000000000000000000000000000000001",
+                "explorer_url": "https://sepolia.etherscan.io",
+                "rpc_quorum": 2
+            }
+        ]
+    }"#;
+
+    fn write_registry(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("teemah-{}-{}.json", name, std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn builtin_registry_looks_up_by_chain_id() {
+        let registry = ChainRegistry::builtin();
+        assert_eq!(registry.default_chain_id(), 97);
+
+        let testnet = registry.get(97).unwrap();
+        assert_eq!(testnet.native_symbol, "tBNB");
+        assert!(testnet.is_deployed());
+        assert_eq!(testnet.explorer_tx_url("0xabc"), "https://testnet.bscscan.com/tx/0xabc");
+        assert!(!registry.get(56).unwrap().is_deployed());
+    }
+
+    #[test]
+    fn unknown_chain_lists_the_supported_ones() {
+        let error = ChainRegistry::builtin().get(1).unwrap_err();
+        assert_eq!(error.chain_id, 1);
+        assert_eq!(error.supported, vec![56, 97]);
+        assert_eq!(error.to_string(), "chain 1 is not supported (supported: 56, 97)");
+    }
+
+    #[test]
+    fn file_fills_defaults_and_picks_the_deployed_chain() {
+        let path = write_registry("load", REGISTRY);
+        let registry = ChainRegistry::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(registry.default_chain_id(), 11155111);
+        let mainnet = registry.get(1).unwrap();
+        assert_eq!(mainnet.rpc_quorum, 1);
+        assert_eq!(mainnet.confirmations, DEFAULT_CONFIRMATIONS);
+        assert_eq!(mainnet.multicall_address, DEFAULT_MULTICALL_ADDRESS);
+        assert_eq!(registry.get(11155111).unwrap().rpc_quorum, 2);
+    }
+
+    #[test]
+    fn env_var_names_the_registry_file() {
+        let path = write_registry("env", REGISTRY);
+        std::env::set_var(CHAINS_PATH_VAR, &path);
+        let registry = ChainRegistry::from_env();
+        std::env::remove_var(CHAINS_PATH_VAR);
+        std::fs::remove_file(&path).unwrap();
+
+        let registry = registry.unwrap();
+        assert_eq!(registry.chains().map(|chain| chain.chain_id).collect::<Vec<_>>(), vec![1, 11155111]);
+    }
+
+    #[test]
+    fn rejects_unservable_quorums_and_duplicate_chains() {
+        let mut chain = ChainRegistry::builtin().get(56).unwrap().clone();
+        chain.rpc_quorum = 2;
+        assert!(ChainRegistry::new(vec![chain.clone()], None).is_err());
+
+        chain.rpc_quorum = 1;
+        assert!(ChainRegistry::new(vec![chain.clone(), chain], None).is_err());
+    }
+}
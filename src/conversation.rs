@@ -0,0 +1,358 @@
+use crate::agent::{Intent, IntentResult};
+use crate::llm::Message;
+use crate::slots::{Clarification, IntentDraft};
+use crate::units::Amount;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Turns kept verbatim per conversation; older turns are folded into the summary.
+const MAX_STORED_TURNS: usize = 20;
+/// Most recent turns replayed to the LLM on each request.
+const MAX_PROMPT_TURNS: usize = 8;
+/// Summary lines kept once turns have been folded away.
+const MAX_SUMMARY_LINES: usize = 12;
+/// Listed projects remembered for follow-ups like "invest in the second one".
+const MAX_REMEMBERED_PROJECTS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationTurn {
+    pub user_input: String,
+    pub intent: Option<Intent>,
+    pub intent_id: Option<String>,
+    pub success: bool,
+    pub ai_message: String,
+    pub data: Option<serde_json::Value>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectRef {
+    pub address: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+}
+
+/// Things the user has referred to that later turns may point back at.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResolvedEntities {
+    pub last_project: Option<ProjectRef>,
+    pub listed_projects: Vec<ProjectRef>,
+    pub last_amount: Option<Amount>,
+    /// Re-runs the last listing one page further, for "show more".
+    pub next_page: Option<IntentDraft>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conversation {
+    pub id: String,
+    pub turns: Vec<ConversationTurn>,
+    pub summary: Vec<String>,
+    pub entities: ResolvedEntities,
+    /// Question the agent is waiting on; the next turn fills its slots.
+    pub pending: Option<Clarification>,
+    /// The intent that asked `pending`, settled once the next turn answers it.
+    #[serde(default)]
+    pub pending_intent_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Conversation {
+    fn new(id: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            id: id.to_string(),
+            turns: Vec::new(),
+            summary: Vec::new(),
+            entities: ResolvedEntities::default(),
+            pending: None,
+            pending_intent_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn push_turn(&mut self, turn: ConversationTurn) {
+        self.remember_entities(&turn);
+        self.turns.push(turn);
+        self.updated_at = Utc::now();
+
+        while self.turns.len() > MAX_STORED_TURNS {
+            let old = self.turns.remove(0);
+            self.summary.push(summarize_turn(&old));
+        }
+        if self.summary.len() > MAX_SUMMARY_LINES {
+            let excess = self.summary.len() - MAX_SUMMARY_LINES;
+            self.summary.drain(..excess);
+        }
+    }
+
+    fn remember_entities(&mut self, turn: &ConversationTurn) {
+        match &turn.intent {
+            Some(Intent::Invest { project_id, amount }) => {
+                self.entities.last_amount = Some(*amount);
+                self.remember_project(project_id);
+            }
+            Some(Intent::ClaimTokens { project_id })
+            | Some(Intent::ClaimRefund { project_id })
+            | Some(Intent::CompleteProject { project_id })
+            | Some(Intent::GetProjectInfo { project_id })
+            | Some(Intent::GetProjectAnalytics { project_id })
+            | Some(Intent::GetTokenBalance { project_id: Some(project_id), .. }) => {
+                self.remember_project(project_id);
+            }
+            _ => {}
+        }
+
+        let Some(data) = &turn.data else { return };
+
+        if let Some(projects) = data.get("projects").and_then(|p| p.as_array()) {
+            let listed: Vec<ProjectRef> = projects
+                .iter()
+                .filter_map(project_ref_from_json)
+                .take(MAX_REMEMBERED_PROJECTS)
+                .collect();
+            if !listed.is_empty() {
+                self.entities.listed_projects = listed;
+            }
+        }
+        if let Some(next_page) = data.get("next_page") {
+            self.entities.next_page = serde_json::from_value(next_page.clone()).ok();
+        }
+        if let Some(project) = data.get("project").and_then(project_ref_from_json) {
+            self.entities.last_project = Some(project);
+        }
+    }
+
+    fn remember_project(&mut self, address: &str) {
+        if address.is_empty() {
+            return;
+        }
+        let known = self.entities.listed_projects
+            .iter()
+            .find(|p| p.address.eq_ignore_ascii_case(address))
+            .cloned();
+        self.entities.last_project = Some(known.unwrap_or(ProjectRef {
+            address: address.to_string(),
+            name: None,
+            symbol: None,
+        }));
+    }
+
+    /// Bounded history for the LLM: a system note with the folded summary and
+    /// known entities, followed by the most recent turns as chat messages.
+    pub fn prompt_messages(&self) -> Vec<Message> {
+        let mut messages = Vec::new();
+
+        let context = self.context_note();
+        if !context.is_empty() {
+            messages.push(Message::system(&context));
+        }
+
+        let start = self.turns.len().saturating_sub(MAX_PROMPT_TURNS);
+        for turn in &self.turns[start..] {
+            messages.push(Message::user(&turn.user_input));
+            if !turn.ai_message.is_empty() {
+                messages.push(Message::assistant(&turn.ai_message));
+            }
+        }
+        messages
+    }
+
+    /// Plain-text summary of earlier turns and remembered entities.
+    pub fn context_note(&self) -> String {
+        let mut note = String::new();
+
+        if !self.summary.is_empty() {
+            note += "Earlier in this conversation:\n";
+            for line in &self.summary {
+                note += &format!("- {}\n", line);
+            }
+        }
+
+        let entities = &self.entities;
+        if !entities.listed_projects.is_empty() {
+            note += "Projects most recently shown to the user, in order:\n";
+            for (i, project) in entities.listed_projects.iter().enumerate() {
+                note += &format!("{}. {}\n", i + 1, describe_project(project));
+            }
+        }
+        if let Some(project) = &entities.last_project {
+            note += &format!("Project last referred to: {}\n", describe_project(project));
+        }
+        if entities.next_page.is_some() {
+            note += "More results are available for the last listing.\n";
+        }
+        if let Some(amount) = entities.last_amount {
+            note += &format!("Last amount used: {}\n", amount);
+        }
+        note
+    }
+}
+
+fn describe_project(project: &ProjectRef) -> String {
+    match (&project.name, &project.symbol) {
+        (Some(name), Some(symbol)) => format!("{} ({}) at {}", name, symbol, project.address),
+        (Some(name), None) => format!("{} at {}", name, project.address),
+        _ => project.address.clone(),
+    }
+}
+
+fn project_ref_from_json(value: &serde_json::Value) -> Option<ProjectRef> {
+    let address = value.get("address")?.as_str()?.to_string();
+    Some(ProjectRef {
+        address,
+        name: value.get("name").and_then(|v| v.as_str()).map(str::to_string),
+        symbol: value.get("symbol").and_then(|v| v.as_str()).map(str::to_string),
+    })
+}
+
+fn summarize_turn(turn: &ConversationTurn) -> String {
+    let outcome = if turn.success { "ok" } else { "failed" };
+    match &turn.intent {
+        Some(intent) => format!("User: \"{}\" → {:?} ({})", turn.user_input, intent, outcome),
+        None => format!("User: \"{}\" ({})", turn.user_input, outcome),
+    }
+}
+
+/// Per-user/session conversation memory shared across agent re-initialization.
+#[derive(Debug, Clone, Default)]
+pub struct ConversationStore {
+    conversations: Arc<RwLock<HashMap<String, Conversation>>>,
+}
+
+impl ConversationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get(&self, id: &str) -> Option<Conversation> {
+        self.conversations.read().await.get(id).cloned()
+    }
+
+    pub async fn record(&self, id: &str, user_input: &str, intent: Option<&Intent>, result: &IntentResult) {
+        let turn = ConversationTurn {
+            user_input: user_input.to_string(),
+            intent: intent.cloned(),
+            intent_id: Some(result.intent_id.clone()),
+            success: result.success,
+            ai_message: result.ai_message.clone(),
+            data: result.data.clone(),
+            timestamp: Utc::now(),
+        };
+
+        let mut conversations = self.conversations.write().await;
+        conversations
+            .entry(id.to_string())
+            .or_insert_with(|| Conversation::new(id))
+            .push_turn(turn);
+    }
+
+    /// Sets or clears the question the agent waits on, with the id of the
+    /// intent that asked it.
+    pub async fn set_pending(&self, id: &str, pending: Option<(String, Clarification)>) {
+        let mut conversations = self.conversations.write().await;
+        let conversation = conversations
+            .entry(id.to_string())
+            .or_insert_with(|| Conversation::new(id));
+        let (intent_id, pending) = pending.unzip();
+        conversation.pending = pending;
+        conversation.pending_intent_id = intent_id;
+        conversation.updated_at = Utc::now();
+    }
+
+    /// Forgets a conversation. Returns whether one existed.
+    pub async fn reset(&self, id: &str) -> bool {
+        self.conversations.write().await.remove(id).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(n: usize, data: Option<serde_json::Value>) -> IntentResult {
+        IntentResult {
+            intent_id: format!("intent-{}", n),
+            success: true,
+            message: String::new(),
+            ai_message: format!("answer {}", n),
+            transaction_data: None,
+            transaction_hash: None,
+            data,
+            clarification: None,
+            simulation: None,
+        }
+    }
+
+    async fn conversation_with_turns(count: usize) -> Conversation {
+        let store = ConversationStore::new();
+        for n in 0..count {
+            store.record("c", &format!("question {}", n), None, &result(n, None)).await;
+        }
+        store.get("c").await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn keeps_recent_turns_in_order_and_folds_the_rest() {
+        let conversation = conversation_with_turns(MAX_STORED_TURNS + 5).await;
+
+        assert_eq!(conversation.turns.len(), MAX_STORED_TURNS);
+        let inputs: Vec<&str> = conversation.turns.iter().map(|t| t.user_input.as_str()).collect();
+        assert_eq!(inputs.first(), Some(&"question 5"));
+        assert_eq!(inputs.last(), Some(&format!("question {}", MAX_STORED_TURNS + 4).as_str()));
+        assert_eq!(conversation.summary.len(), 5);
+        assert!(conversation.summary[0].contains("question 0"));
+    }
+
+    #[tokio::test]
+    async fn summary_keeps_only_the_latest_lines() {
+        let conversation = conversation_with_turns(MAX_STORED_TURNS + MAX_SUMMARY_LINES + 3).await;
+        assert_eq!(conversation.summary.len(), MAX_SUMMARY_LINES);
+        assert!(conversation.summary[0].contains("question 3\""));
+    }
+
+    #[tokio::test]
+    async fn prompt_replays_the_latest_turns_after_the_context_note() {
+        let conversation = conversation_with_turns(MAX_STORED_TURNS + 1).await;
+        let messages = conversation.prompt_messages();
+
+        assert_eq!(messages.len(), 1 + 2 * MAX_PROMPT_TURNS);
+        assert_eq!(messages[0].role, "system");
+        assert!(messages[0].content.starts_with("Earlier in this conversation:"));
+        let last = MAX_STORED_TURNS;
+        assert_eq!(messages[messages.len() - 2].content, format!("question {}", last));
+        assert_eq!(messages[messages.len() - 1].content, format!("answer {}", last));
+        assert_eq!(messages[1].content, format!("question {}", last + 1 - MAX_PROMPT_TURNS));
+    }
+
+    #[tokio::test]
+    async fn remembers_listed_projects_and_the_last_one_used() {
+        let store = ConversationStore::new();
+        let listing = serde_json::json!({ "projects": [
+            { "address": "0x01", "name": "Alpha", "symbol": "ALP" },
+            { "address": "0x02", "name": "Beta" },
+        ]});
+        store.record("c", "list projects", None, &result(0, Some(listing))).await;
+        let intent = Intent::GetProjectInfo { project_id: "0x02".to_string() };
+        store.record("c", "tell me about the second one", Some(&intent), &result(1, None)).await;
+
+        let conversation = store.get("c").await.unwrap();
+        assert_eq!(conversation.entities.listed_projects.len(), 2);
+        let last = conversation.entities.last_project.as_ref().unwrap();
+        assert_eq!(last.name.as_deref(), Some("Beta"));
+        assert!(conversation.context_note().contains("Project last referred to: Beta at 0x02"));
+    }
+
+    #[tokio::test]
+    async fn reset_forgets_the_conversation() {
+        let store = ConversationStore::new();
+        store.record("c", "hi", None, &result(0, None)).await;
+        assert!(store.reset("c").await);
+        assert!(store.get("c").await.is_none());
+        assert!(!store.reset("c").await);
+    }
+}
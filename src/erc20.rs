@@ -0,0 +1,100 @@
+use ethers::{
+    prelude::*,
+    abi::{Detokenize, Token},
+    types::{Address, U256},
+};
+use serde::Serialize;
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use crate::rpc::RpcProvider;
+
+abigen!(
+    Erc20,
+    r#"[
+        function name() external view returns (string)
+        function symbol() external view returns (string)
+        function decimals() external view returns (uint8)
+        function totalSupply() external view returns (uint256)
+        function balanceOf(address) external view returns (uint256)
+        function allowance(address, address) external view returns (uint256)
+    ]"#,
+);
+
+/// An ERC-20 token's metadata, as its contract reports it.
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenInfo {
+    pub address: Address,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub total_supply: U256,
+}
+
+/// Reads ERC-20 contracts on one chain.
+#[derive(Debug, Clone)]
+pub struct TokenReader {
+    client: Arc<RpcProvider>,
+    multicall: Address,
+}
+
+impl TokenReader {
+    pub fn new(provider: RpcProvider, multicall: Address) -> Self {
+        Self { client: Arc::new(provider), multicall }
+    }
+
+    fn contract(&self, token: Address) -> Erc20<RpcProvider> {
+        Erc20::new(token, self.client.clone())
+    }
+
+    /// Name, symbol, decimals and supply in one Multicall3 `eth_call`, or
+    /// four plain calls when the multicall itself fails.
+    pub async fn info(&self, token: Address) -> Result<TokenInfo> {
+        match self.multicall_info(token).await {
+            Ok(info) => Ok(info),
+            Err(e) => {
+                println!("⚠️ Multicall at {:?} failed ({}), reading token {:?} directly", self.multicall, e, token);
+                let contract = self.contract(token);
+                let name = contract.name();
+                let symbol = contract.symbol();
+                let decimals = contract.decimals();
+                let total_supply = contract.total_supply();
+                let (name, symbol, decimals, total_supply) =
+                    tokio::try_join!(name.call(), symbol.call(), decimals.call(), total_supply.call())?;
+                Ok(TokenInfo { address: token, name, symbol, decimals, total_supply })
+            }
+        }
+    }
+
+    async fn multicall_info(&self, token: Address) -> Result<TokenInfo> {
+        let contract = self.contract(token);
+        let mut multicall = Multicall::new(self.client.clone(), Some(self.multicall)).await?;
+        multicall
+            .add_call(contract.name(), true)
+            .add_call(contract.symbol(), true)
+            .add_call(contract.decimals(), true)
+            .add_call(contract.total_supply(), true);
+
+        let mut results = multicall.call_raw().await?.into_iter();
+        let mut next = |call: &str| -> Result<Token> {
+            match results.next() {
+                Some(Ok(token)) => Ok(token),
+                Some(Err(_)) => Err(anyhow!("{:?} is not an ERC-20 token: {}() reverted", token, call)),
+                None => Err(anyhow!("multicall returned no result for {}()", call)),
+            }
+        };
+        let name = String::from_tokens(vec![next("name")?])?;
+        let symbol = String::from_tokens(vec![next("symbol")?])?;
+        let decimals = u8::from_tokens(vec![next("decimals")?])?;
+        let total_supply = U256::from_tokens(vec![next("totalSupply")?])?;
+        Ok(TokenInfo { address: token, name, symbol, decimals, total_supply })
+    }
+
+    pub async fn balance_of(&self, token: Address, owner: Address) -> Result<U256> {
+        Ok(self.contract(token).balance_of(owner).call().await?)
+    }
+
+    /// How much of `owner`'s balance `spender` may move.
+    pub async fn allowance(&self, token: Address, owner: Address, spender: Address) -> Result<U256> {
+        Ok(self.contract(token).allowance(owner, spender).call().await?)
+    }
+}
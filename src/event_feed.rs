@@ -0,0 +1,296 @@
+use crate::indexer::{index_log, launchpad_filter, IndexedEvent};
+use crate::web3::Web3Service;
+use anyhow::{anyhow, Result};
+use ethers::providers::{Middleware, Provider, Ws};
+use ethers::types::{Filter, Log};
+use futures::StreamExt;
+use serde::Serialize;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::AbortHandle;
+
+/// Head and log polling interval when no WebSocket endpoint is configured.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+/// A WebSocket with no new head for this long is treated as dead.
+const HEAD_TIMEOUT: Duration = Duration::from_secs(60);
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+/// Most blocks replayed after a disconnect; the indexer covers anything older.
+const MAX_BACKFILL_BLOCKS: u64 = 5_000;
+/// Events buffered per subscriber before the slowest one starts lagging.
+const FEED_BUFFER: usize = 256;
+
+/// Something that just happened on chain. Launchpad events arrive as soon as
+/// they are mined, before they are final; the indexer still only commits
+/// confirmed blocks.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChainEvent {
+    NewHead { number: u64 },
+    Launchpad(IndexedEvent),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedTransport {
+    WebSocket,
+    HttpPolling,
+}
+
+/// New heads and launchpad logs for one chain, over `eth_subscribe` when the
+/// chain has WebSocket endpoints and by polling over HTTP otherwise. A dropped
+/// WebSocket is reconnected (rotating through the endpoints) and the blocks
+/// missed meanwhile are replayed from HTTP before live events resume.
+#[derive(Debug, Clone)]
+pub struct EventFeed {
+    events: broadcast::Sender<ChainEvent>,
+    transport: FeedTransport,
+    task: AbortHandle,
+}
+
+impl EventFeed {
+    pub fn start(web3_service: Web3Service, ws_urls: Vec<String>) -> Self {
+        let (events, _) = broadcast::channel(FEED_BUFFER);
+        let transport = if ws_urls.is_empty() { FeedTransport::HttpPolling } else { FeedTransport::WebSocket };
+        let sender = events.clone();
+        let handle = tokio::spawn(async move {
+            let mut last_block = None;
+            let mut failures: u32 = 0;
+            for connection in 0.. {
+                let before = last_block;
+                let result = match ws_urls.get(connection % ws_urls.len().max(1)) {
+                    Some(url) => subscribe(&web3_service, url, &sender, &mut last_block).await,
+                    None => poll(&web3_service, &sender, &mut last_block).await,
+                };
+                let Err(e) = result;
+
+                // A connection that delivered anything starts the backoff over
+                failures = if last_block != before { 0 } else { failures + 1 };
+                let backoff = RECONNECT_BACKOFF
+                    .saturating_mul(1 << failures.min(5))
+                    .min(MAX_RECONNECT_BACKOFF);
+                println!("⚠️ Event feed: {} (retrying in {}s)", e, backoff.as_secs());
+                tokio::time::sleep(backoff).await;
+            }
+        });
+        Self {
+            events,
+            transport,
+            task: handle.abort_handle(),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ChainEvent> {
+        self.events.subscribe()
+    }
+
+    /// Waits for the next block from the feed.
+    pub fn heads(&self) -> Heads {
+        Heads { receiver: Some(self.subscribe()) }
+    }
+
+    pub fn transport(&self) -> FeedTransport {
+        self.transport
+    }
+
+    pub fn stop(&self) {
+        self.task.abort();
+    }
+}
+
+/// Paces a polling loop by new heads, so it runs once per block instead of on
+/// a timer. Without a feed it falls back to the timer.
+#[derive(Debug, Default)]
+pub struct Heads {
+    receiver: Option<broadcast::Receiver<ChainEvent>>,
+}
+
+impl Heads {
+    /// Returns at the next new head, or after `fallback` if none arrives.
+    pub async fn wait(&mut self, fallback: Duration) {
+        let Some(receiver) = &mut self.receiver else {
+            tokio::time::sleep(fallback).await;
+            return;
+        };
+        let next_head = async {
+            loop {
+                match receiver.recv().await {
+                    Ok(ChainEvent::NewHead { .. }) | Err(broadcast::error::RecvError::Lagged(_)) => return true,
+                    Ok(ChainEvent::Launchpad(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => return false,
+                }
+            }
+        };
+        match tokio::time::timeout(fallback, next_head).await {
+            Ok(true) | Err(_) => {}
+            Ok(false) => self.receiver = None,
+        }
+    }
+}
+
+/// Follows one WebSocket endpoint until it fails.
+async fn subscribe(
+    web3_service: &Web3Service,
+    url: &str,
+    events: &broadcast::Sender<ChainEvent>,
+    last_block: &mut Option<u64>,
+) -> Result<std::convert::Infallible> {
+    let provider = Provider::<Ws>::connect(url).await?;
+    let filter = launchpad_filter(web3_service.launchpad_address());
+    let mut heads = provider.subscribe_blocks().await?;
+    let mut logs = provider.subscribe_logs(&filter).await?;
+    println!("🔌 Subscribed to new heads and launchpad logs over {}", url);
+
+    // Subscribed first, so nothing falls between the replay and live logs;
+    // live logs the replay already covered are skipped
+    let replayed_to = catch_up(web3_service, &filter, events, last_block).await?;
+
+    loop {
+        tokio::select! {
+            head = tokio::time::timeout(HEAD_TIMEOUT, heads.next()) => match head {
+                Ok(Some(block)) => {
+                    if let Some(number) = block.number.map(|n| n.as_u64()) {
+                        if last_block.is_none_or(|last| number > last) {
+                            *last_block = Some(number);
+                            let _ = events.send(ChainEvent::NewHead { number });
+                        }
+                    }
+                }
+                Ok(None) => return Err(anyhow!("newHeads subscription on {} closed", url)),
+                Err(_) => return Err(anyhow!("no new head from {} in {}s", url, HEAD_TIMEOUT.as_secs())),
+            },
+            log = logs.next() => match log {
+                Some(log) => {
+                    if let Some(event) = launchpad_event(web3_service, &log, Some(replayed_to)) {
+                        let _ = events.send(event);
+                    }
+                }
+                None => return Err(anyhow!("logs subscription on {} closed", url)),
+            },
+        }
+    }
+}
+
+/// Checks for new blocks over HTTP until a request fails.
+async fn poll(
+    web3_service: &Web3Service,
+    events: &broadcast::Sender<ChainEvent>,
+    last_block: &mut Option<u64>,
+) -> Result<std::convert::Infallible> {
+    let filter = launchpad_filter(web3_service.launchpad_address());
+    loop {
+        catch_up(web3_service, &filter, events, last_block).await?;
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Sends the launchpad logs and head for every block after `last_block`, up
+/// to the current head, and returns that head. On first use there is nothing
+/// to replay and the feed starts from the head.
+async fn catch_up(
+    web3_service: &Web3Service,
+    filter: &Filter,
+    events: &broadcast::Sender<ChainEvent>,
+    last_block: &mut Option<u64>,
+) -> Result<u64> {
+    let provider = web3_service.provider();
+    let head = provider.get_block_number().await?.as_u64();
+    let Some(last) = *last_block else {
+        *last_block = Some(head);
+        return Ok(head);
+    };
+    if head <= last {
+        return Ok(last);
+    }
+
+    let from = (last + 1).max(head.saturating_sub(MAX_BACKFILL_BLOCKS - 1));
+    let logs = provider.get_logs(&filter.clone().from_block(from).to_block(head)).await?;
+    if head - last > 1 {
+        println!("🔁 Event feed replayed blocks {}-{} ({} launchpad log(s))", from, head, logs.len());
+    }
+    for log in &logs {
+        if let Some(event) = launchpad_event(web3_service, log, None) {
+            let _ = events.send(event);
+        }
+    }
+    *last_block = Some(head);
+    let _ = events.send(ChainEvent::NewHead { number: head });
+    Ok(head)
+}
+
+/// The feed event for a launchpad log. Logs a reorg removed are dropped, as
+/// are live logs in blocks up to `replayed_to`, which the replay already sent.
+fn launchpad_event(web3_service: &Web3Service, log: &Log, replayed_to: Option<u64>) -> Option<ChainEvent> {
+    let replayed = log.block_number.zip(replayed_to).is_some_and(|(block, to)| block.as_u64() <= to);
+    if log.removed == Some(true) || replayed {
+        return None;
+    }
+    index_log(web3_service, log).map(ChainEvent::Launchpad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launchpad_services::{InvestedFilter, LaunchpadEvent};
+    use ethers::abi::{encode, Token};
+    use ethers::contract::EthEvent;
+    use ethers::types::{Address, H256, U256, U64};
+
+    const LAUNCHPAD: &str = "0xE7392b8ee167980602d38225674bB377De5Fe287";
+
+    fn service() -> Web3Service {
+        Web3Service::new_without_signer("http://127.0.0.1:9", LAUNCHPAD, 97).unwrap()
+    }
+
+    fn invested_log(block: u64, removed: Option<bool>) -> Log {
+        Log {
+            address: LAUNCHPAD.parse().unwrap(),
+            topics: vec![
+                InvestedFilter::signature(),
+                H256::from(Address::repeat_byte(0x01)),
+                H256::from(Address::repeat_byte(0x02)),
+            ],
+            data: encode(&[
+                Token::Uint(U256::from(1_000)),
+                Token::Uint(U256::from(50)),
+                Token::Uint(U256::from(1_700_000_000u64)),
+                Token::Uint(U256::from(1)),
+            ])
+            .into(),
+            block_number: Some(U64::from(block)),
+            transaction_hash: Some(H256::repeat_byte(0x10)),
+            log_index: Some(U256::zero()),
+            removed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn mined_logs_become_launchpad_events() {
+        let Some(ChainEvent::Launchpad(indexed)) = launchpad_event(&service(), &invested_log(100, Some(false)), None) else {
+            panic!("expected a launchpad event");
+        };
+        assert_eq!(indexed.block_number, 100);
+        assert!(matches!(indexed.event, LaunchpadEvent::Invested { amount, .. } if amount == U256::from(1_000)));
+    }
+
+    #[test]
+    fn logs_removed_by_a_reorg_are_dropped() {
+        assert!(launchpad_event(&service(), &invested_log(100, Some(true)), None).is_none());
+        assert!(launchpad_event(&service(), &invested_log(100, Some(true)), Some(50)).is_none());
+    }
+
+    #[test]
+    fn live_logs_the_replay_covered_are_skipped() {
+        let service = service();
+        assert!(launchpad_event(&service, &invested_log(100, None), Some(100)).is_none());
+        assert!(launchpad_event(&service, &invested_log(101, None), Some(100)).is_some());
+    }
+
+    #[test]
+    fn pending_logs_are_not_sent() {
+        let mut log = invested_log(100, None);
+        log.block_number = None;
+        assert!(launchpad_event(&service(), &log, None).is_none());
+    }
+}
@@ -0,0 +1,406 @@
+use crate::rpc::RpcProvider;
+use anyhow::{anyhow, Result};
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{BlockNumber, U256};
+use crate::units::format_gwei;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Recent blocks whose priority fees the suggestions are drawn from.
+const FEE_HISTORY_BLOCKS: u64 = 20;
+/// Reward percentiles read from `eth_feeHistory` for slow, normal and fast.
+const REWARD_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+/// Legacy multipliers over the node's `eth_gasPrice`, in percent, used when
+/// the node has no fee history.
+const LEGACY_MULTIPLIERS: [u64; 3] = [100, 110, 125];
+/// Tip offered on EIP-1559 chains when recent blocks were empty.
+const DEFAULT_PRIORITY_FEE: u64 = 1_000_000_000;
+/// Headroom added to `eth_estimateGas`, in percent, unless a chain sets its own.
+pub const DEFAULT_GAS_LIMIT_BUFFER_PERCENT: u64 = 20;
+/// Limit for a plain launchpad call when estimation fails, e.g. because the
+/// sender is not known yet. Well above what `invest` or a claim uses.
+pub const DEFAULT_CALL_GAS_LIMIT: u64 = 500_000;
+/// Limit for `createProjectWithTokenViaTelegram` when estimation fails; it
+/// deploys a token contract.
+pub const DEFAULT_DEPLOY_GAS_LIMIT: u64 = 6_000_000;
+
+/// How a chain prices gas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeModel {
+    /// `maxFeePerGas` and `maxPriorityFeePerGas` over the block's base fee.
+    Eip1559,
+    /// A single `gasPrice`.
+    Legacy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasSpeed {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+/// What to pay per unit of gas, in wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum GasFees {
+    Eip1559 { max_fee_per_gas: U256, max_priority_fee_per_gas: U256 },
+    Legacy { gas_price: U256 },
+}
+
+impl GasFees {
+    /// The most one unit of gas can cost.
+    pub fn max_price(&self) -> U256 {
+        match self {
+            GasFees::Eip1559 { max_fee_per_gas, .. } => *max_fee_per_gas,
+            GasFees::Legacy { gas_price } => *gas_price,
+        }
+    }
+}
+
+impl fmt::Display for GasFees {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GasFees::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas } => write!(
+                f,
+                "max fee {} gwei, tip {} gwei",
+                format_gwei(*max_fee_per_gas),
+                format_gwei(*max_priority_fee_per_gas)
+            ),
+            GasFees::Legacy { gas_price } => write!(f, "gas price {} gwei", format_gwei(*gas_price)),
+        }
+    }
+}
+
+/// Fee suggestions for the next block.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeSuggestions {
+    pub model: FeeModel,
+    /// Base fee of the next block, on chains that have one.
+    pub base_fee: Option<U256>,
+    pub slow: GasFees,
+    pub normal: GasFees,
+    pub fast: GasFees,
+}
+
+impl FeeSuggestions {
+    pub fn at(&self, speed: GasSpeed) -> GasFees {
+        match speed {
+            GasSpeed::Slow => self.slow,
+            GasSpeed::Normal => self.normal,
+            GasSpeed::Fast => self.fast,
+        }
+    }
+}
+
+/// Gas limit and fees for one transaction.
+#[derive(Debug, Clone, Copy)]
+pub struct GasPlan {
+    pub gas_limit: U256,
+    /// Whether `gas_limit` came from `eth_estimateGas` rather than a default.
+    pub estimated: bool,
+    pub fees: GasFees,
+}
+
+impl GasPlan {
+    /// The most the transaction's gas can cost, in wei.
+    pub fn max_cost(&self) -> U256 {
+        self.gas_limit * self.fees.max_price()
+    }
+}
+
+/// Prices transactions for one chain: EIP-1559 or legacy fees drawn from
+/// recent blocks, and gas limits estimated with headroom but never above what
+/// a block can hold.
+#[derive(Debug, Clone)]
+pub struct GasStrategy {
+    provider: RpcProvider,
+    /// Configured fee model; detected from the latest block when unset.
+    fee_model: Option<FeeModel>,
+    buffer_percent: u64,
+}
+
+impl GasStrategy {
+    pub fn new(provider: RpcProvider, fee_model: Option<FeeModel>, buffer_percent: u64) -> Self {
+        Self { provider, fee_model, buffer_percent }
+    }
+
+    pub async fn suggest_fees(&self) -> Result<FeeSuggestions> {
+        let latest = self
+            .provider
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| anyhow!("node returned no latest block"))?;
+        let model = self.fee_model.unwrap_or(match latest.base_fee_per_gas {
+            Some(_) => FeeModel::Eip1559,
+            None => FeeModel::Legacy,
+        });
+
+        let history = self
+            .provider
+            .fee_history(FEE_HISTORY_BLOCKS, BlockNumber::Latest, &REWARD_PERCENTILES)
+            .await;
+        let (base_fee, tips) = match history {
+            Ok(history) => {
+                // The last entry is the base fee of the block after the newest
+                let base_fee = history.base_fee_per_gas.last().copied().or(latest.base_fee_per_gas);
+                let tips = (0..REWARD_PERCENTILES.len())
+                    .map(|index| {
+                        let rewards = history
+                            .reward
+                            .iter()
+                            .zip(&history.gas_used_ratio)
+                            .filter(|(_, used)| **used > 0.0)
+                            .filter_map(|(rewards, _)| rewards.get(index).copied());
+                        median(rewards)
+                    })
+                    .collect::<Vec<_>>();
+                (base_fee, Some(tips))
+            }
+            Err(e) => {
+                println!("⚠️ eth_feeHistory unavailable, pricing from eth_gasPrice: {}", e);
+                (latest.base_fee_per_gas, None)
+            }
+        };
+
+        let [slow, normal, fast] = match model {
+            FeeModel::Eip1559 => {
+                let base_fee = base_fee.unwrap_or_default();
+                let fallback_tip = match tips.as_ref().and_then(|tips| tips[1]) {
+                    Some(tip) => tip,
+                    None => self.node_priority_fee().await,
+                };
+                [0, 1, 2].map(|level| {
+                    let tip = tips.as_ref().and_then(|tips| tips[level]).unwrap_or(fallback_tip);
+                    // Room for the base fee to double before the transaction is priced out
+                    GasFees::Eip1559 {
+                        max_fee_per_gas: base_fee * 2 + tip,
+                        max_priority_fee_per_gas: tip,
+                    }
+                })
+            }
+            FeeModel::Legacy => {
+                // The node's price is the floor validators accept
+                let node_price = self.provider.get_gas_price().await?;
+                [0, 1, 2].map(|level| {
+                    let gas_price = match tips.as_ref().and_then(|tips| tips[level]) {
+                        Some(tip) => (base_fee.unwrap_or_default() + tip).max(node_price),
+                        None => node_price * LEGACY_MULTIPLIERS[level] / 100,
+                    };
+                    GasFees::Legacy { gas_price }
+                })
+            }
+        };
+
+        Ok(FeeSuggestions { model, base_fee, slow, normal, fast })
+    }
+
+    /// Gas limit for `tx`: the node's estimate plus the buffer, capped at the
+    /// block gas limit. Falls back to `fallback` when estimation fails.
+    pub async fn gas_limit(&self, tx: &TypedTransaction, fallback: u64) -> (U256, bool) {
+        let block_limit = match self.provider.get_block(BlockNumber::Latest).await {
+            Ok(Some(block)) => Some(block.gas_limit),
+            _ => None,
+        };
+        let cap = |limit: U256| block_limit.map_or(limit, |block_limit| limit.min(block_limit));
+
+        match self.provider.estimate_gas(tx, None).await {
+            Ok(estimate) => (cap(estimate * (100 + self.buffer_percent) / 100), true),
+            Err(e) => {
+                println!("⚠️ Gas estimation failed, using {} gas: {}", fallback, e);
+                (cap(U256::from(fallback)), false)
+            }
+        }
+    }
+
+    pub async fn plan(&self, tx: &TypedTransaction, speed: GasSpeed, fallback_limit: u64) -> Result<GasPlan> {
+        let (gas_limit, estimated) = self.gas_limit(tx, fallback_limit).await;
+        let fees = self.suggest_fees().await?.at(speed);
+        Ok(GasPlan { gas_limit, estimated, fees })
+    }
+
+    async fn node_priority_fee(&self) -> U256 {
+        self.provider
+            .request::<_, U256>("eth_maxPriorityFeePerGas", ())
+            .await
+            .unwrap_or_else(|_| U256::from(DEFAULT_PRIORITY_FEE))
+    }
+}
+
+fn median(values: impl Iterator<Item = U256>) -> Option<U256> {
+    let mut values: Vec<U256> = values.collect();
+    values.sort();
+    values.get(values.len() / 2).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::tests::stub_endpoint;
+    use crate::rpc::FailoverClient;
+    use serde_json::{json, Value};
+
+    fn gwei(values: &[u64]) -> Vec<U256> {
+        values.iter().map(|v| U256::from(*v) * U256::exp10(9)).collect()
+    }
+
+    #[test]
+    fn median_picks_the_middle_value() {
+        assert_eq!(median(gwei(&[3, 1, 2]).into_iter()), Some(gwei(&[2])[0]));
+        assert_eq!(median(gwei(&[5, 1, 9, 7, 3]).into_iter()), Some(gwei(&[5])[0]));
+    }
+
+    #[test]
+    fn median_of_an_even_count_takes_the_upper_middle() {
+        assert_eq!(median(gwei(&[4, 1, 3, 2]).into_iter()), Some(gwei(&[3])[0]));
+    }
+
+    #[test]
+    fn median_of_nothing_is_none() {
+        assert_eq!(median(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn max_cost_uses_the_highest_price() {
+        let plan = GasPlan {
+            gas_limit: U256::from(100_000),
+            estimated: true,
+            fees: GasFees::Eip1559 {
+                max_fee_per_gas: U256::from(30),
+                max_priority_fee_per_gas: U256::from(2),
+            },
+        };
+        assert_eq!(plan.max_cost(), U256::from(3_000_000));
+    }
+
+    fn strategy(url: String, fee_model: Option<FeeModel>) -> GasStrategy {
+        let provider = FailoverClient::new(&[url]).unwrap().provider();
+        GasStrategy::new(provider, fee_model, DEFAULT_GAS_LIMIT_BUFFER_PERCENT)
+    }
+
+    fn block(base_fee: Option<&str>) -> Value {
+        let mut block = json!({
+            "number": "0x10",
+            "hash": format!("0x{}", "11".repeat(32)),
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "timestamp": "0x0",
+            "transactions": [],
+        });
+        if let Some(base_fee) = base_fee {
+            block["baseFeePerGas"] = json!(base_fee);
+        }
+        json!({ "result": block })
+    }
+
+    /// A London node: 10 gwei base fee, three busy blocks tipping 1-5 gwei
+    /// and an empty one whose rewards should be ignored.
+    fn london_node(request: &Value) -> Value {
+        match request["method"].as_str() {
+            Some("eth_getBlockByNumber") => block(Some("0x2540be400")),
+            Some("eth_feeHistory") => json!({ "result": {
+                "oldestBlock": "0xd",
+                "baseFeePerGas": ["0x2540be400", "0x2540be400", "0x2540be400", "0x2540be400", "0x2540be400"],
+                "gasUsedRatio": [0.5, 0.0, 0.6, 0.7],
+                "reward": [
+                    ["0x3b9aca00", "0x77359400", "0xb2d05e00"],
+                    ["0x174876e800", "0x174876e800", "0x174876e800"],
+                    ["0x77359400", "0xb2d05e00", "0xee6b2800"],
+                    ["0xb2d05e00", "0xee6b2800", "0x12a05f200"],
+                ],
+            }}),
+            _ => json!({ "error": { "code": -32601, "message": "method not found" } }),
+        }
+    }
+
+    /// A London node whose recent blocks were all empty, so tips come from
+    /// `eth_maxPriorityFeePerGas` (3 gwei).
+    fn idle_london_node(request: &Value) -> Value {
+        match request["method"].as_str() {
+            Some("eth_getBlockByNumber") => block(Some("0x2540be400")),
+            Some("eth_feeHistory") => json!({ "result": {
+                "oldestBlock": "0xf",
+                "baseFeePerGas": ["0x2540be400", "0x2540be400"],
+                "gasUsedRatio": [0.0],
+                "reward": [["0x0", "0x0", "0x0"]],
+            }}),
+            Some("eth_maxPriorityFeePerGas") => json!({ "result": "0xb2d05e00" }),
+            _ => json!({ "error": { "code": -32601, "message": "method not found" } }),
+        }
+    }
+
+    /// A pre-London node with no base fee and no `eth_feeHistory`, quoting
+    /// 5 gwei from `eth_gasPrice`.
+    fn legacy_node(request: &Value) -> Value {
+        match request["method"].as_str() {
+            Some("eth_getBlockByNumber") => block(None),
+            Some("eth_gasPrice") => json!({ "result": "0x12a05f200" }),
+            _ => json!({ "error": { "code": -32601, "message": "method not found" } }),
+        }
+    }
+
+    fn london_node_quoting_13_gwei(request: &Value) -> Value {
+        match request["method"].as_str() {
+            Some("eth_gasPrice") => json!({ "result": "0x306dc4200" }),
+            _ => london_node(request),
+        }
+    }
+
+    fn eip1559(max_fee: u64, tip: u64) -> GasFees {
+        GasFees::Eip1559 {
+            max_fee_per_gas: gwei(&[max_fee])[0],
+            max_priority_fee_per_gas: gwei(&[tip])[0],
+        }
+    }
+
+    #[tokio::test]
+    async fn eip1559_tips_are_medians_of_busy_blocks() {
+        let (url, _) = stub_endpoint(london_node).await;
+        let fees = strategy(url, None).suggest_fees().await.unwrap();
+
+        assert_eq!(fees.model, FeeModel::Eip1559);
+        assert_eq!(fees.base_fee, Some(gwei(&[10])[0]));
+        // Each level has room for the base fee to double
+        assert_eq!(fees.slow, eip1559(22, 2));
+        assert_eq!(fees.normal, eip1559(23, 3));
+        assert_eq!(fees.fast, eip1559(24, 4));
+    }
+
+    #[tokio::test]
+    async fn eip1559_falls_back_to_the_node_tip_after_empty_blocks() {
+        let (url, _) = stub_endpoint(idle_london_node).await;
+        let fees = strategy(url, None).suggest_fees().await.unwrap();
+
+        assert_eq!(fees.model, FeeModel::Eip1559);
+        assert_eq!(fees.slow, eip1559(23, 3));
+        assert_eq!(fees.fast, eip1559(23, 3));
+    }
+
+    #[tokio::test]
+    async fn legacy_prices_scale_the_node_gas_price() {
+        let (url, _) = stub_endpoint(legacy_node).await;
+        let fees = strategy(url, None).suggest_fees().await.unwrap();
+
+        assert_eq!(fees.model, FeeModel::Legacy);
+        assert_eq!(fees.base_fee, None);
+        assert_eq!(fees.slow, GasFees::Legacy { gas_price: U256::from(5_000_000_000u64) });
+        assert_eq!(fees.normal, GasFees::Legacy { gas_price: U256::from(5_500_000_000u64) });
+        assert_eq!(fees.fast, GasFees::Legacy { gas_price: U256::from(6_250_000_000u64) });
+    }
+
+    #[tokio::test]
+    async fn configured_legacy_model_never_prices_below_the_node() {
+        let (url, _) = stub_endpoint(london_node_quoting_13_gwei).await;
+        let fees = strategy(url, Some(FeeModel::Legacy)).suggest_fees().await.unwrap();
+
+        // Base fee plus the median tip, floored at the node's price
+        assert_eq!(fees.model, FeeModel::Legacy);
+        assert_eq!(fees.slow, GasFees::Legacy { gas_price: gwei(&[13])[0] });
+        assert_eq!(fees.normal, GasFees::Legacy { gas_price: gwei(&[13])[0] });
+        assert_eq!(fees.fast, GasFees::Legacy { gas_price: gwei(&[14])[0] });
+    }
+}
@@ -0,0 +1,566 @@
+use crate::event_feed::EventFeed;
+use crate::launchpad_services::{
+    InvestedFilter, LaunchpadEvent, ProjectCreatedFilter, ProjectStatusChangedFilter, RefundClaimedFilter, TokensClaimedFilter,
+};
+use crate::web3::Web3Service;
+use anyhow::Result;
+use ethers::contract::EthEvent;
+use ethers::providers::Middleware;
+use ethers::types::{Address, Filter, Log, H256, U256};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::AbortHandle;
+
+pub const DEFAULT_INDEX_PATH: &str = "launchpad_index.db";
+/// Blocks per `eth_getLogs` request; halved while the node rejects the range
+/// and doubled back after each range it accepts.
+const LOG_CHUNK_BLOCKS: u64 = 5_000;
+const POLL_INTERVAL: Duration = Duration::from_secs(6);
+
+/// A launchpad event together with where it was emitted.
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexedEvent {
+    pub block_number: u64,
+    pub tx_hash: H256,
+    pub log_index: u64,
+    #[serde(flatten)]
+    pub event: LaunchpadEvent,
+}
+
+impl IndexedEvent {
+    /// The claim a `TokensClaimed` or `RefundClaimed` event records.
+    pub fn claim(&self) -> Option<Claim> {
+        let (project, investor, kind) = match self.event {
+            LaunchpadEvent::TokensClaimed { project, investor, .. } => (project, investor, ClaimKind::Tokens),
+            LaunchpadEvent::RefundClaimed { project, investor, .. } => (project, investor, ClaimKind::Refund),
+            _ => return None,
+        };
+        Some(Claim {
+            project,
+            account: investor,
+            kind,
+            tx_hash: self.tx_hash,
+            block_number: self.block_number,
+        })
+    }
+}
+
+/// What a confirmed `claimTokens` or `claimRefund` transaction claimed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimKind {
+    Tokens,
+    Refund,
+}
+
+impl ClaimKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ClaimKind::Tokens => "tokens",
+            ClaimKind::Refund => "refund",
+        }
+    }
+}
+
+/// A claim made by `account` on a project. Indexed from the launchpad's
+/// claim events, and recorded early by the receipt watcher for claims sent
+/// through this backend; both are keyed by transaction, so they never double up.
+#[derive(Debug, Clone, Serialize)]
+pub struct Claim {
+    pub project: Address,
+    pub account: Address,
+    pub kind: ClaimKind,
+    pub tx_hash: H256,
+    pub block_number: u64,
+}
+
+/// How far the index has got for one launchpad contract.
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexStats {
+    pub contract: Address,
+    pub last_block: Option<u64>,
+    pub projects_created: u64,
+    pub investments: u64,
+    pub status_changes: u64,
+}
+
+/// SQLite-backed store of decoded launchpad events. Rows are keyed by
+/// `(tx_hash, log_index)`, so re-indexing a range never duplicates them.
+#[derive(Debug, Clone)]
+pub struct EventStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl EventStore {
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS indexer_state (
+                contract TEXT PRIMARY KEY,
+                last_block INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS projects_created (
+                contract TEXT NOT NULL,
+                block_number INTEGER NOT NULL,
+                tx_hash TEXT NOT NULL,
+                log_index INTEGER NOT NULL,
+                project TEXT NOT NULL,
+                creator TEXT NOT NULL,
+                name TEXT NOT NULL,
+                PRIMARY KEY (tx_hash, log_index)
+            );
+            CREATE TABLE IF NOT EXISTS investments (
+                contract TEXT NOT NULL,
+                block_number INTEGER NOT NULL,
+                tx_hash TEXT NOT NULL,
+                log_index INTEGER NOT NULL,
+                project TEXT NOT NULL,
+                investor TEXT NOT NULL,
+                amount TEXT NOT NULL,
+                token_amount TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                tier INTEGER NOT NULL,
+                PRIMARY KEY (tx_hash, log_index)
+            );
+            CREATE INDEX IF NOT EXISTS investments_by_investor ON investments (contract, investor);
+            CREATE INDEX IF NOT EXISTS investments_by_project ON investments (contract, project);
+            CREATE TABLE IF NOT EXISTS status_changes (
+                contract TEXT NOT NULL,
+                block_number INTEGER NOT NULL,
+                tx_hash TEXT NOT NULL,
+                log_index INTEGER NOT NULL,
+                project TEXT NOT NULL,
+                status INTEGER NOT NULL,
+                PRIMARY KEY (tx_hash, log_index)
+            );
+            CREATE TABLE IF NOT EXISTS claims (
+                contract TEXT NOT NULL,
+                block_number INTEGER NOT NULL,
+                tx_hash TEXT PRIMARY KEY,
+                project TEXT NOT NULL,
+                account TEXT NOT NULL,
+                kind TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS claims_by_account ON claims (contract, account);",
+        )?;
+        Ok(Self { conn: Arc::new(Mutex::new(conn)) })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Connection> {
+        // A panic mid-write rolls its transaction back, so the data is still sound
+        self.conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn last_block(&self, contract: Address) -> Result<Option<u64>> {
+        let last = self
+            .lock()
+            .query_row(
+                "SELECT last_block FROM indexer_state WHERE contract = ?1",
+                params![hex(contract)],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()?;
+        Ok(last.map(|block| block as u64))
+    }
+
+    /// Stores a range's events and advances the checkpoint in one transaction,
+    /// so a restart resumes right after the last range that fully landed.
+    pub fn commit(&self, contract: Address, events: &[IndexedEvent], to_block: u64) -> Result<()> {
+        let mut conn = self.lock();
+        let tx = conn.transaction()?;
+        let contract = hex(contract);
+        for indexed in events {
+            let block = indexed.block_number as i64;
+            let hash = format!("{:?}", indexed.tx_hash);
+            let log_index = indexed.log_index as i64;
+            match &indexed.event {
+                LaunchpadEvent::ProjectCreated { project, creator, name } => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO projects_created
+                         (contract, block_number, tx_hash, log_index, project, creator, name)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![contract, block, hash, log_index, hex(*project), hex(*creator), name],
+                    )?;
+                }
+                LaunchpadEvent::Invested { project, investor, amount, token_amount, timestamp, tier } => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO investments
+                         (contract, block_number, tx_hash, log_index, project, investor, amount, token_amount, timestamp, tier)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                        params![
+                            contract,
+                            block,
+                            hash,
+                            log_index,
+                            hex(*project),
+                            hex(*investor),
+                            amount.to_string(),
+                            token_amount.to_string(),
+                            timestamp.min(&U256::from(i64::MAX)).as_u64() as i64,
+                            tier,
+                        ],
+                    )?;
+                }
+                LaunchpadEvent::ProjectStatusChanged { project, status } => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO status_changes
+                         (contract, block_number, tx_hash, log_index, project, status)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![contract, block, hash, log_index, hex(*project), status],
+                    )?;
+                }
+                LaunchpadEvent::TokensClaimed { .. } | LaunchpadEvent::RefundClaimed { .. } => {
+                    if let Some(claim) = indexed.claim() {
+                        insert_claim(&tx, &contract, &claim)?;
+                    }
+                }
+            }
+        }
+        tx.execute(
+            "INSERT INTO indexer_state (contract, last_block) VALUES (?1, ?2)
+             ON CONFLICT (contract) DO UPDATE SET last_block = excluded.last_block",
+            params![contract, to_block as i64],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn record_claim(&self, contract: Address, claim: &Claim) -> Result<()> {
+        insert_claim(&self.lock(), &hex(contract), claim)
+    }
+
+    /// Indexed `Invested` events by `investor`, optionally in one project,
+    /// oldest first.
+    pub fn investments(&self, contract: Address, investor: Address, project: Option<Address>) -> Result<Vec<IndexedEvent>> {
+        let conn = self.lock();
+        let mut statement = conn.prepare(
+            "SELECT block_number, tx_hash, log_index, project, amount, token_amount, timestamp, tier
+             FROM investments
+             WHERE contract = ?1 AND investor = ?2 AND (?3 IS NULL OR project = ?3)
+             ORDER BY block_number, log_index",
+        )?;
+        let rows = statement.query_map(params![hex(contract), hex(investor), project.map(hex)], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, i64>(6)?,
+                row.get::<_, u8>(7)?,
+            ))
+        })?;
+
+        let mut investments = Vec::new();
+        for row in rows {
+            let (block_number, tx_hash, log_index, project, amount, token_amount, timestamp, tier) = row?;
+            investments.push(IndexedEvent {
+                block_number: block_number as u64,
+                tx_hash: H256::from_str(&tx_hash)?,
+                log_index: log_index as u64,
+                event: LaunchpadEvent::Invested {
+                    project: Address::from_str(&project)?,
+                    investor,
+                    amount: U256::from_dec_str(&amount)?,
+                    token_amount: U256::from_dec_str(&token_amount)?,
+                    timestamp: U256::from(timestamp as u64),
+                    tier,
+                },
+            });
+        }
+        Ok(investments)
+    }
+
+    pub fn claims(&self, contract: Address, account: Address) -> Result<Vec<Claim>> {
+        let conn = self.lock();
+        let mut statement = conn.prepare(
+            "SELECT block_number, tx_hash, project, kind FROM claims
+             WHERE contract = ?1 AND account = ?2
+             ORDER BY block_number",
+        )?;
+        let rows = statement.query_map(params![hex(contract), hex(account)], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut claims = Vec::new();
+        for row in rows {
+            let (block_number, tx_hash, project, kind) = row?;
+            claims.push(Claim {
+                project: Address::from_str(&project)?,
+                account,
+                kind: if kind == ClaimKind::Refund.as_str() { ClaimKind::Refund } else { ClaimKind::Tokens },
+                tx_hash: H256::from_str(&tx_hash)?,
+                block_number: block_number as u64,
+            });
+        }
+        Ok(claims)
+    }
+
+    pub fn stats(&self, contract: Address) -> Result<IndexStats> {
+        let last_block = self.last_block(contract)?;
+        let conn = self.lock();
+        let count = |table: &str| -> Result<u64> {
+            let sql = format!("SELECT COUNT(*) FROM {} WHERE contract = ?1", table);
+            Ok(conn.query_row(&sql, params![hex(contract)], |row| row.get::<_, i64>(0))? as u64)
+        };
+        Ok(IndexStats {
+            contract,
+            last_block,
+            projects_created: count("projects_created")?,
+            investments: count("investments")?,
+            status_changes: count("status_changes")?,
+        })
+    }
+}
+
+/// Keeps the local event store in step with the chain: backfills from the
+/// launchpad's deployment block, then follows the head, stopping short of the
+/// last few blocks so a shallow reorg never reaches the index.
+#[derive(Debug)]
+pub struct EventIndexer {
+    web3_service: Web3Service,
+    store: EventStore,
+    deployment_block: u64,
+    confirmations: u64,
+    feed: Option<EventFeed>,
+    task: Option<AbortHandle>,
+}
+
+impl EventIndexer {
+    pub fn new(web3_service: Web3Service, store: EventStore, deployment_block: u64, confirmations: u64) -> Self {
+        Self {
+            web3_service,
+            store,
+            deployment_block,
+            confirmations: confirmations.max(1),
+            feed: None,
+            task: None,
+        }
+    }
+
+    /// Syncs on every new head from `feed` rather than on a timer.
+    pub fn with_feed(mut self, feed: &EventFeed) -> Self {
+        self.feed = Some(feed.clone());
+        self
+    }
+
+    /// Runs the indexer in the background until `stop` is called.
+    pub fn start(mut self) -> Self {
+        let web3_service = self.web3_service.clone();
+        let store = self.store.clone();
+        let (deployment_block, confirmations) = (self.deployment_block, self.confirmations);
+        let mut heads = self.feed.as_ref().map(EventFeed::heads).unwrap_or_default();
+        let handle = tokio::spawn(async move {
+            let contract = web3_service.launchpad_address();
+            println!("🗂️ Indexing launchpad events for {:?} from block {}", contract, deployment_block);
+            loop {
+                if let Err(e) = sync(&web3_service, &store, deployment_block, confirmations).await {
+                    // Retried on the next tick from the last committed block
+                    println!("⚠️ Event indexer: {}", e);
+                }
+                heads.wait(POLL_INTERVAL).await;
+            }
+        });
+        self.task = Some(handle.abort_handle());
+        self
+    }
+
+    pub fn stop(&self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+
+    pub fn contract(&self) -> Address {
+        self.web3_service.launchpad_address()
+    }
+
+    pub async fn stats(&self) -> Result<IndexStats> {
+        let store = self.store.clone();
+        let contract = self.contract();
+        tokio::task::spawn_blocking(move || store.stats(contract)).await?
+    }
+}
+
+/// Indexes every block from the checkpoint up to the head, less the blocks
+/// that are not yet confirmed.
+async fn sync(web3_service: &Web3Service, store: &EventStore, deployment_block: u64, confirmations: u64) -> Result<()> {
+    let contract = web3_service.launchpad_address();
+    let provider = web3_service.provider();
+
+    let checkpoint = {
+        let store = store.clone();
+        tokio::task::spawn_blocking(move || store.last_block(contract)).await??
+    };
+    let mut from = checkpoint.map_or(deployment_block, |last| (last + 1).max(deployment_block));
+    let head = provider.get_block_number().await?.as_u64();
+    let Some(safe_head) = (head + 1).checked_sub(confirmations) else {
+        return Ok(());
+    };
+
+    let filter = launchpad_filter(contract);
+    let mut span = LOG_CHUNK_BLOCKS;
+    while from <= safe_head {
+        let to = (from + span - 1).min(safe_head);
+        let logs = match provider.get_logs(&filter.clone().from_block(from).to_block(to)).await {
+            Ok(logs) => logs,
+            Err(e) if span > 1 => {
+                span /= 2;
+                println!("⚠️ eth_getLogs {}-{} failed ({}), retrying {} blocks at a time", from, to, e, span);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let events: Vec<IndexedEvent> = logs.iter().filter_map(|log| index_log(web3_service, log)).collect();
+        if !events.is_empty() {
+            println!("🗂️ Indexed {} launchpad event(s) in blocks {}-{}", events.len(), from, to);
+        }
+        let store = store.clone();
+        tokio::task::spawn_blocking(move || store.commit(contract, &events, to)).await??;
+        from = to + 1;
+        span = (span * 2).min(LOG_CHUNK_BLOCKS);
+    }
+    Ok(())
+}
+
+/// Every launchpad event the index keeps.
+pub fn launchpad_filter(contract: Address) -> Filter {
+    Filter::new().address(contract).topic0(vec![
+        ProjectCreatedFilter::signature(),
+        InvestedFilter::signature(),
+        ProjectStatusChangedFilter::signature(),
+        TokensClaimedFilter::signature(),
+        RefundClaimedFilter::signature(),
+    ])
+}
+
+/// The launchpad event in `log` with its position, once the log is mined.
+pub fn index_log(web3_service: &Web3Service, log: &Log) -> Option<IndexedEvent> {
+    let event = web3_service.decode_launchpad_events(std::slice::from_ref(log)).pop()?;
+    Some(IndexedEvent {
+        block_number: log.block_number?.as_u64(),
+        tx_hash: log.transaction_hash?,
+        log_index: log.log_index?.as_u64(),
+        event,
+    })
+}
+
+fn insert_claim(conn: &Connection, contract: &str, claim: &Claim) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO claims (contract, block_number, tx_hash, project, account, kind)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            contract,
+            claim.block_number as i64,
+            format!("{:?}", claim.tx_hash),
+            hex(claim.project),
+            hex(claim.account),
+            claim.kind.as_str(),
+        ],
+    )?;
+    Ok(())
+}
+
+fn hex(address: Address) -> String {
+    format!("{:?}", address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract() -> Address {
+        Address::repeat_byte(0xaa)
+    }
+
+    fn invested(block_number: u64, tx: u8, log_index: u64) -> IndexedEvent {
+        IndexedEvent {
+            block_number,
+            tx_hash: H256::repeat_byte(tx),
+            log_index,
+            event: LaunchpadEvent::Invested {
+                project: Address::repeat_byte(0x01),
+                investor: Address::repeat_byte(0x02),
+                amount: U256::from(1_000),
+                token_amount: U256::from(50),
+                timestamp: U256::from(1_700_000_000u64),
+                tier: 1,
+            },
+        }
+    }
+
+    fn refund_claimed(block_number: u64, tx: u8) -> IndexedEvent {
+        IndexedEvent {
+            block_number,
+            tx_hash: H256::repeat_byte(tx),
+            log_index: 0,
+            event: LaunchpadEvent::RefundClaimed {
+                project: Address::repeat_byte(0x01),
+                investor: Address::repeat_byte(0x02),
+                amount: U256::from(1_000),
+            },
+        }
+    }
+
+    #[test]
+    fn commit_is_idempotent() {
+        let store = EventStore::open(":memory:").unwrap();
+        let events = vec![invested(10, 0x10, 0), invested(11, 0x11, 3)];
+        store.commit(contract(), &events, 20).unwrap();
+        store.commit(contract(), &events, 20).unwrap();
+
+        let stats = store.stats(contract()).unwrap();
+        assert_eq!(stats.investments, 2);
+        assert_eq!(stats.last_block, Some(20));
+    }
+
+    #[test]
+    fn duplicate_logs_are_skipped() {
+        let store = EventStore::open(":memory:").unwrap();
+        // The same log seen twice, e.g. from overlapping ranges after a retry
+        store.commit(contract(), &[invested(10, 0x10, 0), invested(10, 0x10, 0)], 10).unwrap();
+        store.commit(contract(), &[invested(10, 0x10, 0), invested(10, 0x10, 1)], 12).unwrap();
+
+        let investments = store.investments(contract(), Address::repeat_byte(0x02), None).unwrap();
+        assert_eq!(investments.iter().map(|i| i.log_index).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn last_block_resumes_from_the_latest_commit() {
+        let store = EventStore::open(":memory:").unwrap();
+        assert_eq!(store.last_block(contract()).unwrap(), None);
+
+        store.commit(contract(), &[], 100).unwrap();
+        store.commit(contract(), &[invested(150, 0x15, 0)], 200).unwrap();
+        assert_eq!(store.last_block(contract()).unwrap(), Some(200));
+        // Checkpoints are per contract
+        assert_eq!(store.last_block(Address::repeat_byte(0xbb)).unwrap(), None);
+    }
+
+    #[test]
+    fn claim_events_and_watched_claims_share_a_row() {
+        let store = EventStore::open(":memory:").unwrap();
+        let event = refund_claimed(30, 0x30);
+        let claim = event.claim().unwrap();
+        assert_eq!(claim.kind, ClaimKind::Refund);
+
+        store.record_claim(contract(), &claim).unwrap();
+        store.commit(contract(), &[event], 40).unwrap();
+
+        let claims = store.claims(contract(), Address::repeat_byte(0x02)).unwrap();
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].kind, ClaimKind::Refund);
+        assert_eq!(claims[0].block_number, 30);
+        assert!(invested(10, 0x10, 0).claim().is_none());
+    }
+}
@@ -0,0 +1,506 @@
+use crate::agent::{Intent, IntentResult};
+use crate::launchpad_services::LaunchpadEvent;
+use chrono::{DateTime, Duration, Utc};
+use ethers::types::Address;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+
+/// How long a prepared transaction may wait for the user's signature, and a
+/// clarification for its answer.
+const SIGNATURE_TIMEOUT_MINUTES: i64 = 30;
+/// Records kept in memory. The oldest finished ones are dropped first, then
+/// the oldest that never got as far as a transaction.
+const MAX_RECORDS: usize = 1000;
+/// Updates buffered per subscriber before the slowest one starts lagging.
+const UPDATE_BUFFER: usize = 256;
+
+/// Where an intent is in its life, from parsing to the chain's verdict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntentState {
+    Parsed,
+    Prepared,
+    AwaitingSignature,
+    Submitted,
+    Confirmed,
+    Failed,
+    Expired,
+    /// A clarification the user answered; the answer carries on as another intent.
+    Superseded,
+}
+
+impl IntentState {
+    pub fn is_terminal(self) -> bool {
+        matches!(self, IntentState::Confirmed | IntentState::Failed | IntentState::Expired | IntentState::Superseded)
+    }
+
+    /// Allowed moves. Read-only intents finish straight from `Prepared`,
+    /// clarifications are superseded once answered or expire while `Parsed`,
+    /// and anything unfinished can fail.
+    pub fn can_become(self, next: IntentState) -> bool {
+        use IntentState::*;
+        match (self, next) {
+            (Parsed, Prepared) | (Parsed, Expired) | (Parsed, Superseded) => true,
+            (Prepared, AwaitingSignature) | (Prepared, Confirmed) => true,
+            (AwaitingSignature, Submitted) | (AwaitingSignature, Expired) => true,
+            (Submitted, Confirmed) | (Submitted, Expired) => true,
+            (from, Failed) => !from.is_terminal(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateChange {
+    pub state: IntentState,
+    pub at: DateTime<Utc>,
+    pub note: Option<String>,
+}
+
+/// What the chain said about a submitted transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptOutcome {
+    /// Receipt `status`: 1 for success, 0 for a revert.
+    pub status: u64,
+    /// The account that sent the transaction.
+    pub from: Address,
+    pub block_number: u64,
+    pub confirmations: u64,
+    pub gas_used: Option<String>,
+    pub events: Vec<LaunchpadEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentRecord {
+    pub intent_id: String,
+    pub conversation_id: String,
+    pub user_id: i64,
+    pub user_input: String,
+    pub intent: Option<Intent>,
+    pub state: IntentState,
+    pub history: Vec<StateChange>,
+    pub result: Option<IntentResult>,
+    pub tx_hash: Option<String>,
+    pub receipt: Option<ReceiptOutcome>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// The intent that answered this one's clarification.
+    #[serde(default)]
+    pub superseded_by: Option<String>,
+}
+
+impl IntentRecord {
+    /// Moves a transaction the user never signed, or a question never
+    /// answered, to `Expired` once its deadline has passed.
+    fn expire_if_stale(&mut self, now: DateTime<Utc>) -> bool {
+        if self.expires_at.is_none_or(|at| at > now) {
+            return false;
+        }
+        let note = if self.state == IntentState::Parsed { "clarification not answered" } else { "not signed in time" };
+        self.move_to(IntentState::Expired, Some(note.to_string())).is_ok()
+    }
+
+    fn move_to(&mut self, state: IntentState, note: Option<String>) -> Result<(), IntentStoreError> {
+        if !self.state.can_become(state) {
+            return Err(IntentStoreError::InvalidTransition { from: self.state, to: state });
+        }
+        let now = Utc::now();
+        self.state = state;
+        self.updated_at = now;
+        self.expires_at = match state {
+            IntentState::AwaitingSignature => Some(now + Duration::minutes(SIGNATURE_TIMEOUT_MINUTES)),
+            _ => None,
+        };
+        self.history.push(StateChange { state, at: now, note });
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum IntentStoreError {
+    NotFound(String),
+    InvalidTransition { from: IntentState, to: IntentState },
+}
+
+impl fmt::Display for IntentStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntentStoreError::NotFound(id) => write!(f, "intent not found: {}", id),
+            IntentStoreError::InvalidTransition { from, to } => {
+                write!(f, "cannot move intent from {:?} to {:?}", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IntentStoreError {}
+
+/// Filters for listing intents; unset fields match everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IntentQuery {
+    pub conversation_id: Option<String>,
+    pub state: Option<IntentState>,
+    pub limit: Option<usize>,
+}
+
+/// Every intent the agent has handled, shared across agent re-initialization.
+/// Each change is also broadcast so clients can follow an intent live.
+#[derive(Debug, Clone)]
+pub struct IntentStore {
+    records: Arc<RwLock<HashMap<String, IntentRecord>>>,
+    updates: broadcast::Sender<IntentRecord>,
+}
+
+impl Default for IntentStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntentStore {
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_BUFFER);
+        Self {
+            records: Arc::new(RwLock::new(HashMap::new())),
+            updates,
+        }
+    }
+    
+    /// Every record as it changes, from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<IntentRecord> {
+        self.updates.subscribe()
+    }
+
+    /// Starts tracking an intent the moment its input has been parsed.
+    pub async fn record_parsed(&self, intent_id: &str, conversation_id: &str, user_id: i64, user_input: &str, intent: Option<&Intent>) {
+        let now = Utc::now();
+        let record = IntentRecord {
+            intent_id: intent_id.to_string(),
+            conversation_id: conversation_id.to_string(),
+            user_id,
+            user_input: user_input.to_string(),
+            intent: intent.cloned(),
+            state: IntentState::Parsed,
+            history: vec![StateChange { state: IntentState::Parsed, at: now, note: None }],
+            result: None,
+            tx_hash: None,
+            receipt: None,
+            created_at: now,
+            updated_at: now,
+            expires_at: None,
+            superseded_by: None,
+        };
+
+        let mut records = self.records.write().await;
+        while records.len() >= MAX_RECORDS {
+            evict_oldest(&mut records);
+        }
+        records.insert(intent_id.to_string(), record.clone());
+        let _ = self.updates.send(record);
+    }
+
+    /// Attaches the execution result and moves the intent on: to
+    /// `AwaitingSignature` when there is a transaction to sign, to `Confirmed`
+    /// for read-only answers, or to `Failed`. Clarifications stay `Parsed`
+    /// until answered or expired.
+    pub async fn record_result(&self, result: &IntentResult) -> Result<IntentRecord, IntentStoreError> {
+        self.update(&result.intent_id, |record| {
+            record.result = Some(result.clone());
+            if result.clarification.is_some() {
+                let now = Utc::now();
+                record.updated_at = now;
+                record.expires_at = Some(now + Duration::minutes(SIGNATURE_TIMEOUT_MINUTES));
+                return Ok(());
+            }
+            if !result.success {
+                return record.move_to(IntentState::Failed, Some(result.message.clone()));
+            }
+            record.move_to(IntentState::Prepared, None)?;
+            match &result.transaction_data {
+                Some(tx) => record.move_to(IntentState::AwaitingSignature, Some(tx.description.clone())),
+                None => record.move_to(IntentState::Confirmed, Some("read-only, nothing to sign".to_string())),
+            }
+        })
+        .await
+    }
+
+    pub async fn mark_submitted(&self, intent_id: &str, tx_hash: &str) -> Result<IntentRecord, IntentStoreError> {
+        self.update(intent_id, |record| {
+            record.move_to(IntentState::Submitted, Some(tx_hash.to_string()))?;
+            record.tx_hash = Some(tx_hash.to_string());
+            Ok(())
+        })
+        .await
+    }
+
+    /// Settles a submitted intent from its receipt: `Confirmed` when the
+    /// transaction succeeded, `Failed` when it reverted.
+    pub async fn mark_receipt(&self, intent_id: &str, outcome: ReceiptOutcome) -> Result<IntentRecord, IntentStoreError> {
+        self.update(intent_id, |record| {
+            let (state, note) = if outcome.status == 1 {
+                (IntentState::Confirmed, format!("confirmed in block {}", outcome.block_number))
+            } else {
+                (IntentState::Failed, format!("reverted in block {}", outcome.block_number))
+            };
+            record.move_to(state, Some(note))?;
+            record.receipt = Some(outcome);
+            Ok(())
+        })
+        .await
+    }
+
+    /// A submitted transaction that never got a receipt, most likely dropped
+    /// or replaced.
+    pub async fn mark_dropped(&self, intent_id: &str, reason: &str) -> Result<IntentRecord, IntentStoreError> {
+        self.update(intent_id, |record| record.move_to(IntentState::Expired, Some(reason.to_string())))
+            .await
+    }
+
+    /// Settles a clarification once the user has answered it, pointing at
+    /// the intent the answer became.
+    pub async fn mark_superseded(&self, intent_id: &str, by: &str) -> Result<IntentRecord, IntentStoreError> {
+        self.update(intent_id, |record| {
+            record.move_to(IntentState::Superseded, Some(format!("answered in intent {}", by)))?;
+            record.superseded_by = Some(by.to_string());
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn mark_failed(&self, intent_id: &str, reason: &str) -> Result<IntentRecord, IntentStoreError> {
+        self.update(intent_id, |record| record.move_to(IntentState::Failed, Some(reason.to_string())))
+            .await
+    }
+
+    pub async fn get(&self, intent_id: &str) -> Option<IntentRecord> {
+        self.expire_stale().await;
+        self.records.read().await.get(intent_id).cloned()
+    }
+
+    /// Matching records, newest first.
+    pub async fn list(&self, query: &IntentQuery) -> Vec<IntentRecord> {
+        self.expire_stale().await;
+        let records = self.records.read().await;
+        let mut matching: Vec<IntentRecord> = records
+            .values()
+            .filter(|r| query.conversation_id.as_ref().is_none_or(|id| &r.conversation_id == id))
+            .filter(|r| query.state.is_none_or(|state| r.state == state))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+        if let Some(limit) = query.limit {
+            matching.truncate(limit);
+        }
+        matching
+    }
+
+    /// Intents that have not reached a terminal state.
+    pub async fn active_count(&self) -> usize {
+        self.expire_stale().await;
+        self.records.read().await.values().filter(|r| !r.state.is_terminal()).count()
+    }
+
+    /// Expires transactions the user never signed and questions never answered.
+    async fn expire_stale(&self) {
+        let now = Utc::now();
+        let mut records = self.records.write().await;
+        for record in records.values_mut() {
+            if record.expire_if_stale(now) {
+                let _ = self.updates.send(record.clone());
+            }
+        }
+    }
+
+    async fn update<F>(&self, intent_id: &str, apply: F) -> Result<IntentRecord, IntentStoreError>
+    where
+        F: FnOnce(&mut IntentRecord) -> Result<(), IntentStoreError>,
+    {
+        let mut records = self.records.write().await;
+        let record = records
+            .get_mut(intent_id)
+            .ok_or_else(|| IntentStoreError::NotFound(intent_id.to_string()))?;
+        // A deadline that passed since the last sweep still counts, e.g. a
+        // transaction submitted after its signature window closed
+        if record.expire_if_stale(Utc::now()) {
+            let _ = self.updates.send(record.clone());
+        }
+        apply(record)?;
+        let _ = self.updates.send(record.clone());
+        Ok(record.clone())
+    }
+}
+
+/// Drops one record: the oldest finished one, else the oldest with no
+/// transaction out yet, else the oldest of all so the map stays bounded.
+fn evict_oldest(records: &mut HashMap<String, IntentRecord>) {
+    let rank = |state: IntentState| match state {
+        state if state.is_terminal() => 0,
+        IntentState::Parsed | IntentState::Prepared => 1,
+        _ => 2,
+    };
+    let oldest = records
+        .values()
+        .min_by_key(|r| (rank(r.state), r.updated_at))
+        .map(|r| r.intent_id.clone());
+    if let Some(id) = oldest {
+        records.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::TransactionData;
+    use IntentState::*;
+
+    fn result(intent_id: &str, success: bool, with_tx: bool) -> IntentResult {
+        IntentResult {
+            intent_id: intent_id.to_string(),
+            success,
+            message: if success { "ok" } else { "failed" }.to_string(),
+            ai_message: String::new(),
+            transaction_data: with_tx.then(|| TransactionData {
+                to: format!("{:?}", Address::zero()),
+                data: "0x".to_string(),
+                value: "0".to_string(),
+                chain_id: 97,
+                description: "Invest".to_string(),
+                gas: None,
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                gas_price: None,
+            }),
+            transaction_hash: None,
+            data: None,
+            clarification: None,
+            simulation: None,
+        }
+    }
+
+    fn receipt(status: u64) -> ReceiptOutcome {
+        ReceiptOutcome {
+            status,
+            from: Address::zero(),
+            block_number: 42,
+            confirmations: 3,
+            gas_used: None,
+            events: Vec::new(),
+        }
+    }
+
+    async fn awaiting_signature(store: &IntentStore, intent_id: &str) {
+        store.record_parsed(intent_id, "conversation", 1, "invest 1 in X", None).await;
+        store.record_result(&result(intent_id, true, true)).await.unwrap();
+    }
+
+    #[test]
+    fn legal_transitions() {
+        for (from, to) in [
+            (Parsed, Prepared),
+            (Parsed, Expired),
+            (Parsed, Superseded),
+            (Prepared, AwaitingSignature),
+            (Prepared, Confirmed),
+            (AwaitingSignature, Submitted),
+            (AwaitingSignature, Expired),
+            (Submitted, Confirmed),
+            (Submitted, Expired),
+            (Parsed, Failed),
+            (Submitted, Failed),
+        ] {
+            assert!(from.can_become(to), "{:?} -> {:?}", from, to);
+        }
+    }
+
+    #[test]
+    fn illegal_transitions() {
+        for (from, to) in [
+            (Parsed, AwaitingSignature),
+            (Parsed, Submitted),
+            (Prepared, Submitted),
+            (AwaitingSignature, Confirmed),
+            (Prepared, Superseded),
+            (Confirmed, Failed),
+            (Expired, Submitted),
+            (Failed, Confirmed),
+            (Superseded, Prepared),
+        ] {
+            assert!(!from.can_become(to), "{:?} -> {:?}", from, to);
+        }
+    }
+
+    #[tokio::test]
+    async fn read_only_results_confirm_and_transactions_await_signature() {
+        let store = IntentStore::new();
+        store.record_parsed("read", "conversation", 1, "list projects", None).await;
+        let record = store.record_result(&result("read", true, false)).await.unwrap();
+        assert_eq!(record.state, Confirmed);
+
+        awaiting_signature(&store, "write").await;
+        let record = store.get("write").await.unwrap();
+        assert_eq!(record.state, AwaitingSignature);
+        assert!(record.expires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn receipts_settle_submitted_intents() {
+        let store = IntentStore::new();
+        awaiting_signature(&store, "ok").await;
+        store.mark_submitted("ok", "0xabc").await.unwrap();
+        let record = store.mark_receipt("ok", receipt(1)).await.unwrap();
+        assert_eq!(record.state, Confirmed);
+        assert_eq!(record.tx_hash.as_deref(), Some("0xabc"));
+        assert_eq!(record.receipt.unwrap().block_number, 42);
+
+        awaiting_signature(&store, "reverted").await;
+        store.mark_submitted("reverted", "0xdef").await.unwrap();
+        assert_eq!(store.mark_receipt("reverted", receipt(0)).await.unwrap().state, Failed);
+
+        // Nothing was sent yet, so there is no receipt to settle on
+        awaiting_signature(&store, "unsent").await;
+        let error = store.mark_receipt("unsent", receipt(1)).await.unwrap_err();
+        assert!(matches!(error, IntentStoreError::InvalidTransition { from: AwaitingSignature, to: Confirmed }));
+    }
+
+    #[tokio::test]
+    async fn submitting_after_the_signature_window_fails() {
+        let store = IntentStore::new();
+        awaiting_signature(&store, "late").await;
+        store.records.write().await.get_mut("late").unwrap().expires_at = Some(Utc::now() - Duration::seconds(1));
+
+        let error = store.mark_submitted("late", "0xabc").await.unwrap_err();
+        assert!(matches!(error, IntentStoreError::InvalidTransition { from: Expired, to: Submitted }));
+        assert_eq!(store.get("late").await.unwrap().state, Expired);
+    }
+
+    #[tokio::test]
+    async fn answered_clarifications_are_superseded() {
+        let store = IntentStore::new();
+        store.record_parsed("question", "conversation", 1, "invest in X", None).await;
+        let record = store.mark_superseded("question", "answer").await.unwrap();
+        assert_eq!(record.state, Superseded);
+        assert_eq!(record.superseded_by.as_deref(), Some("answer"));
+        assert!(record.expires_at.is_none());
+        assert_eq!(store.active_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn eviction_keeps_the_store_bounded() {
+        let store = IntentStore::new();
+        awaiting_signature(&store, "signing").await;
+        for i in 0..MAX_RECORDS + 5 {
+            store.record_parsed(&format!("parsed-{}", i), "conversation", 1, "hi", None).await;
+        }
+
+        let records = store.records.read().await;
+        assert_eq!(records.len(), MAX_RECORDS);
+        // The oldest untouched records went first; the one awaiting a signature stays
+        assert!(records.contains_key("signing"));
+        assert!(!records.contains_key("parsed-0"));
+        assert!(records.contains_key(&format!("parsed-{}", MAX_RECORDS + 4)));
+    }
+}
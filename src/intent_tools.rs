@@ -0,0 +1,298 @@
+use crate::llm::{ToolCall, ToolDefinition};
+use crate::slots::IntentDraft;
+use serde_json::json;
+use std::fmt;
+
+/// Why a model response could not be turned into an `Intent`.
+#[derive(Debug, Clone)]
+pub enum IntentParseError {
+    MultipleToolCalls(usize),
+    UnknownTool(String),
+    MalformedArguments { tool: String, reason: String },
+}
+
+impl fmt::Display for IntentParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntentParseError::MultipleToolCalls(count) => {
+                write!(f, "expected exactly one tool call, model returned {}", count)
+            }
+            IntentParseError::UnknownTool(name) => write!(f, "model called unknown tool '{}'", name),
+            IntentParseError::MalformedArguments { tool, reason } => {
+                write!(f, "malformed arguments for '{}': {}", tool, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IntentParseError {}
+
+struct IntentTool {
+    name: &'static str,
+    description: &'static str,
+    parameters: fn() -> serde_json::Value,
+}
+
+fn no_parameters() -> serde_json::Value {
+    json!({ "type": "object", "properties": {}, "additionalProperties": false })
+}
+
+fn project_id_parameters() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "project_id": { "type": "string", "description": "Project name, token symbol, or contract address" }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn listing_parameters() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "limit": { "type": "integer", "description": "How many projects to rank (default 10, at most 50)" },
+            "cursor": { "type": "string", "description": "next_cursor from an earlier page of the same listing" }
+        },
+        "additionalProperties": false
+    })
+}
+
+const INTENT_TOOLS: &[IntentTool] = &[
+    IntentTool {
+        name: "CreateProject",
+        description: "Create a new token/project on the launchpad",
+        parameters: || json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "description": "Project name" },
+                "symbol": { "type": "string", "description": "Token ticker symbol, e.g. MTK" }
+            },
+            "additionalProperties": false
+        }),
+    },
+    IntentTool {
+        name: "Invest",
+        description: "Invest native currency in a launchpad project",
+        parameters: || json!({
+            "type": "object",
+            "properties": {
+                "project_id": { "type": "string", "description": "Project name, token symbol, or contract address" },
+                "amount": { "type": "string", "description": "Amount to invest in whole native units, e.g. \"0.5\", \"1.5k\" or \"500 gwei\"; \"all\" for the whole balance" }
+            },
+            "additionalProperties": false
+        }),
+    },
+    IntentTool {
+        name: "ClaimTokens",
+        description: "Claim purchased tokens from a project",
+        parameters: project_id_parameters,
+    },
+    IntentTool {
+        name: "ClaimRefund",
+        description: "Claim back an investment from a project whose raise ended below its soft cap",
+        parameters: project_id_parameters,
+    },
+    IntentTool {
+        name: "CompleteProject",
+        description: "Finalize a project whose raise succeeded so tokens can be claimed",
+        parameters: project_id_parameters,
+    },
+    IntentTool {
+        name: "GetProjectInfo",
+        description: "Show information about a single project",
+        parameters: project_id_parameters,
+    },
+    IntentTool {
+        name: "GetProjectAnalytics",
+        description: "Show fundraising analytics for a project: raise velocity, investor count, average ticket and projected completion",
+        parameters: project_id_parameters,
+    },
+    IntentTool {
+        name: "ListProjects",
+        description: "List the projects available on the launchpad",
+        parameters: no_parameters,
+    },
+    IntentTool {
+        name: "SearchProjects",
+        description: "Search launchpad projects by name or token symbol",
+        parameters: || json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string", "description": "Name or symbol to search for" },
+                "cursor": { "type": "string", "description": "next_cursor from an earlier page of the same search" }
+            },
+            "additionalProperties": false
+        }),
+    },
+    IntentTool {
+        name: "TrendingProjects",
+        description: "Show the launchpad's currently trending projects",
+        parameters: listing_parameters,
+    },
+    IntentTool {
+        name: "NewProjects",
+        description: "Show the most recently launched projects",
+        parameters: listing_parameters,
+    },
+    IntentTool {
+        name: "ProjectsByStatus",
+        description: "List projects in a given status, one page at a time",
+        parameters: || json!({
+            "type": "object",
+            "properties": {
+                "status": {
+                    "type": "string",
+                    "enum": ["pending", "approved", "rejected", "active", "successful", "failed", "completed"],
+                    "description": "Project status"
+                },
+                "page": { "type": "integer", "description": "Page number, starting at 1" }
+            },
+            "additionalProperties": false
+        }),
+    },
+    IntentTool {
+        name: "GetUserBalance",
+        description: "Check the connected wallet's balance",
+        parameters: no_parameters,
+    },
+    IntentTool {
+        name: "MyInvestments",
+        description: "List the connected wallet's investments in launchpad projects, with their claim and refund state",
+        parameters: no_parameters,
+    },
+    IntentTool {
+        name: "GetTokenBalance",
+        description: "Check how much of an ERC-20 token the connected wallet holds, such as tokens claimed from a project",
+        parameters: || json!({
+            "type": "object",
+            "properties": {
+                "token": { "type": "string", "description": "Token contract address, when the user gives one" },
+                "project_id": { "type": "string", "description": "Project whose token to check: its contract address, name or symbol" }
+            },
+            "additionalProperties": false
+        }),
+    },
+    IntentTool {
+        name: "GetProjectStatistics",
+        description: "Show aggregate launchpad statistics",
+        parameters: no_parameters,
+    },
+];
+
+/// JSON-schema tool definitions for every actionable `Intent` variant.
+pub fn intent_tool_definitions() -> Vec<ToolDefinition> {
+    INTENT_TOOLS
+        .iter()
+        .map(|tool| ToolDefinition::function(tool.name, tool.description, (tool.parameters)()))
+        .collect()
+}
+
+/// Decodes the model's tool calls into an `IntentDraft`. No tool call means
+/// the model could not map the message to an action, which is `Unknown`.
+/// Missing or badly typed arguments are left for slot validation; only
+/// structural problems are parse errors.
+pub fn decode_tool_calls(calls: &[ToolCall]) -> Result<IntentDraft, IntentParseError> {
+    match calls {
+        [] => Ok(IntentDraft::unknown()),
+        [call] => decode_tool_call(call),
+        _ => Err(IntentParseError::MultipleToolCalls(calls.len())),
+    }
+}
+
+pub fn decode_tool_call(call: &ToolCall) -> Result<IntentDraft, IntentParseError> {
+    let name = call.function.name.as_str();
+    let tool = INTENT_TOOLS
+        .iter()
+        .find(|tool| tool.name == name)
+        .ok_or_else(|| IntentParseError::UnknownTool(name.to_string()))?;
+    let malformed = |reason: String| IntentParseError::MalformedArguments {
+        tool: name.to_string(),
+        reason,
+    };
+
+    let raw = call.function.arguments.trim();
+    let arguments: serde_json::Value = if raw.is_empty() {
+        json!({})
+    } else {
+        serde_json::from_str(raw).map_err(|e| malformed(e.to_string()))?
+    };
+    let serde_json::Value::Object(arguments) = arguments else {
+        return Err(malformed("arguments must be a JSON object".to_string()));
+    };
+
+    let parameters = (tool.parameters)();
+    let properties = parameters["properties"].as_object().cloned().unwrap_or_default();
+
+    let mut draft = IntentDraft::new(name);
+    for (field, value) in arguments {
+        if !properties.contains_key(&field) {
+            return Err(malformed(format!("unexpected argument '{}'", field)));
+        }
+        // Models sometimes send null or "" for arguments they don't know
+        let empty = value.is_null() || value.as_str().is_some_and(|s| s.trim().is_empty());
+        if !empty {
+            draft = draft.with_slot(&field, value);
+        }
+    }
+    Ok(draft)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::FunctionCall;
+    use crate::slots;
+
+    fn call(name: &str, arguments: &str) -> ToolCall {
+        ToolCall {
+            id: "call_1".to_string(),
+            function: FunctionCall { name: name.to_string(), arguments: arguments.to_string() },
+        }
+    }
+
+    #[test]
+    fn decodes_a_well_formed_call() {
+        let draft = decode_tool_call(&call("Invest", r#"{"project_id": "PEPE", "amount": "0.5"}"#)).unwrap();
+        assert_eq!(draft.intent, "Invest");
+        assert_eq!(draft.slots["project_id"], "PEPE");
+        assert_eq!(draft.slots["amount"], "0.5");
+    }
+
+    #[test]
+    fn rejects_an_unknown_tool() {
+        let error = decode_tool_call(&call("TransferEverything", "{}")).unwrap_err();
+        assert!(matches!(error, IntentParseError::UnknownTool(name) if name == "TransferEverything"));
+    }
+
+    #[test]
+    fn rejects_unexpected_arguments() {
+        let error = decode_tool_call(&call("Invest", r#"{"project_id": "PEPE", "amount": "1", "recipient": "0x0"}"#)).unwrap_err();
+        assert!(matches!(error, IntentParseError::MalformedArguments { reason, .. } if reason.contains("recipient")));
+    }
+
+    #[test]
+    fn rejects_arguments_that_are_not_a_json_object() {
+        for arguments in [r#"["PEPE", "1"]"#, r#""PEPE""#, "42", r#"{"project_id": "PEPE""#, "not json"] {
+            let error = decode_tool_call(&call("Invest", arguments)).unwrap_err();
+            assert!(matches!(error, IntentParseError::MalformedArguments { .. }), "{}", arguments);
+        }
+    }
+
+    #[test]
+    fn no_tool_call_is_unknown_and_several_are_an_error() {
+        assert_eq!(decode_tool_calls(&[]).unwrap().intent, "Unknown");
+
+        let calls = [call("GetAllProjects", "{}"), call("Invest", r#"{"project_id": "PEPE", "amount": "1"}"#)];
+        assert!(matches!(decode_tool_calls(&calls), Err(IntentParseError::MultipleToolCalls(2))));
+    }
+
+    #[test]
+    fn partial_invest_asks_instead_of_investing_nothing() {
+        for arguments in ["", "{}", r#"{"project_id": "PEPE"}"#, r#"{"project_id": "", "amount": null}"#] {
+            let draft = decode_tool_call(&call("Invest", arguments)).unwrap();
+            let clarification = slots::resolve(draft).expect_err(arguments);
+            assert!(clarification.missing_fields.contains(&"amount".to_string()), "{}", arguments);
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use ethers::{
     prelude::*,
-    types::{Address, U256, Filter, ValueOrArray},
-    abi::{encode, decode, ParamType, Token, Detokenize, InvalidOutputType},
+    types::{Address, U256},
+    abi::{encode, decode, ParamType, Tokenizable, Detokenize, InvalidOutputType},
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use anyhow::Result;
+use crate::rpc::{FailoverClient, RpcProvider};
+use crate::simulation::decode_revert;
 
 abigen!(
     MechaLaunchpad,
@@ -29,15 +31,24 @@ abigen!(
         event ProjectCreated(address indexed , address indexed , uint256 , string )
         event Invested(address indexed , address indexed , uint256 , uint256 , uint256 , uint8 )
         event ProjectStatusChanged(address indexed , uint8 )
+        event TokensClaimed(address indexed , address indexed , uint256 )
+        event RefundClaimed(address indexed , address indexed , uint256 )
     ]"#,
 );
+/// A launchpad project, with every field `getProject` returns.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
+    pub address: Address,
     pub creator: Address,
+    pub offering_token: Address,
     pub name: String,
     pub symbol: String,
-    pub decimal: u8,
-    pub initial_supply: U256,
+    pub soft_cap: U256,
+    pub hard_cap: U256,
+    pub total_raised: U256,
+    pub start_time: U256,
+    pub end_time: U256,
+    pub status: ProjectStatus,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +59,27 @@ pub struct ProjectDetails {
     pub telegram_group: String,
 }
 
+/// A project's marketing allocation, as `getProjectMarketingInfo` returns
+/// it. The contract leaves its outputs unnamed; fields follow their order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketingInfo {
+    /// Wei set aside for marketing the project.
+    pub allocation: U256,
+    /// Wallet the marketing funds are paid to.
+    pub wallet: Address,
+    /// Wei of the allocation released so far.
+    pub released: U256,
+    pub is_active: bool,
+    /// Unix time of the last change to the allocation.
+    pub last_updated: U256,
+}
+
+impl MarketingInfo {
+    pub fn is_empty(&self) -> bool {
+        self.allocation.is_zero() && self.wallet.is_zero() && self.released.is_zero() && !self.is_active && self.last_updated.is_zero()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserInfo {
     pub tier: u8,
@@ -74,14 +106,266 @@ pub struct ProjectSummary {
     pub name: String,
     pub total_raised: U256,
     pub hard_cap: U256,
-   // pub status: u8,
-    pub progress: u64,
+    pub status: ProjectStatus,
+    /// Share of the hard cap raised, in percent.
+    pub progress: f64,
+}
+
+/// On-chain `uint8` project status, in contract order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Active,
+    Successful,
+    Failed,
+    Completed,
+}
+
+impl ProjectStatus {
+    pub const ALL: [ProjectStatus; 7] = [
+        ProjectStatus::Pending,
+        ProjectStatus::Approved,
+        ProjectStatus::Rejected,
+        ProjectStatus::Active,
+        ProjectStatus::Successful,
+        ProjectStatus::Failed,
+        ProjectStatus::Completed,
+    ];
+    
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
+    
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            ProjectStatus::Pending => "pending",
+            ProjectStatus::Approved => "approved",
+            ProjectStatus::Rejected => "rejected",
+            ProjectStatus::Active => "active",
+            ProjectStatus::Successful => "successful",
+            ProjectStatus::Failed => "failed",
+            ProjectStatus::Completed => "completed",
+        }
+    }
+    
+    /// Parses a status name, accepting a few everyday synonyms ("live", "funded").
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        let canonical = match name.as_str() {
+            "live" | "open" | "ongoing" => "active",
+            "success" | "succeeded" | "funded" => "successful",
+            "finished" | "finalized" | "finalised" => "completed",
+            other => other,
+        };
+        Self::ALL.into_iter().find(|status| status.name() == canonical)
+    }
+}
+
+/// Where a raise stands, derived from its caps and sale window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FundingPhase {
+    Upcoming,
+    Live,
+    Succeeded,
+    Failed,
+}
+
+/// Figures derived from a project's raise at a given moment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectMetrics {
+    pub phase: FundingPhase,
+    /// Share of the hard cap raised so far, in percent (two decimals).
+    pub progress_percent: f64,
+    /// Seconds until the sale ends; zero once it has ended.
+    pub time_remaining_secs: u64,
+    pub soft_cap_reached: bool,
+    pub hard_cap_reached: bool,
+}
+
+impl Project {
+    /// A raise succeeds once the hard cap is filled, or when the sale ends
+    /// with the soft cap met; it fails when the sale ends short of the soft cap.
+    pub fn phase(&self, now: u64) -> FundingPhase {
+        let now = U256::from(now);
+        let ended = !self.end_time.is_zero() && now >= self.end_time;
+        
+        if self.hard_cap_reached() || (ended && self.soft_cap_reached()) {
+            FundingPhase::Succeeded
+        } else if ended {
+            FundingPhase::Failed
+        } else if now < self.start_time {
+            FundingPhase::Upcoming
+        } else {
+            FundingPhase::Live
+        }
+    }
+    
+    /// Why an investment would be refused right now, if it would be. The
+    /// admin-set status wins over what times and caps suggest.
+    pub fn invest_refusal(&self, now: u64) -> Option<&'static str> {
+        if let Some(reason) = self.inactive_refusal() {
+            return Some(reason);
+        }
+        match self.phase(now) {
+            FundingPhase::Live => None,
+            FundingPhase::Upcoming => Some("This raise has not started yet. You can invest once the sale opens."),
+            FundingPhase::Succeeded | FundingPhase::Failed => Some("This raise has ended, so it no longer accepts investments."),
+        }
+    }
+    
+    /// Refunds only exist for raises that ended short of the soft cap, or
+    /// that an admin marked failed.
+    pub fn refund_refusal(&self, now: u64) -> Option<&'static str> {
+        match self.status {
+            ProjectStatus::Failed => return None,
+            ProjectStatus::Successful | ProjectStatus::Completed => {
+                return Some("This project reached its soft cap, so investments are not refundable. You can claim your tokens instead.");
+            }
+            _ => {}
+        }
+        if let Some(reason) = self.inactive_refusal() {
+            return Some(reason);
+        }
+        match self.phase(now) {
+            FundingPhase::Failed => None,
+            FundingPhase::Succeeded => Some("This project reached its soft cap, so investments are not refundable. You can claim your tokens instead."),
+            FundingPhase::Live => Some("This raise is still running. Refunds only open if it ends without reaching its soft cap."),
+            FundingPhase::Upcoming => Some("This raise has not started yet, so there is nothing to refund."),
+        }
+    }
+    
+    pub fn complete_refusal(&self, now: u64) -> Option<&'static str> {
+        match self.status {
+            ProjectStatus::Successful => return None,
+            ProjectStatus::Completed => return Some("This project has already been completed."),
+            ProjectStatus::Failed => {
+                return Some("This raise ended below its soft cap, so it cannot be completed. Investors can claim refunds instead.");
+            }
+            _ => {}
+        }
+        if let Some(reason) = self.inactive_refusal() {
+            return Some(reason);
+        }
+        match self.phase(now) {
+            FundingPhase::Succeeded => None,
+            FundingPhase::Failed => Some("This raise ended below its soft cap, so it cannot be completed. Investors can claim refunds instead."),
+            FundingPhase::Live => Some("This raise is still running. It can be completed once the hard cap is filled or the sale ends above the soft cap."),
+            FundingPhase::Upcoming => Some("This raise has not started yet, so it cannot be completed."),
+        }
+    }
+    
+    fn inactive_refusal(&self) -> Option<&'static str> {
+        match self.status {
+            ProjectStatus::Active => None,
+            ProjectStatus::Pending | ProjectStatus::Approved => Some("This project has not been opened for investment yet."),
+            ProjectStatus::Rejected => Some("This project was rejected by the launchpad."),
+            ProjectStatus::Successful | ProjectStatus::Completed => Some("This raise has ended, so it no longer accepts investments."),
+            ProjectStatus::Failed => Some("This raise has failed. Investors can claim refunds instead."),
+        }
+    }
+    
+    pub fn soft_cap_reached(&self) -> bool {
+        self.total_raised >= self.soft_cap
+    }
+    
+    pub fn hard_cap_reached(&self) -> bool {
+        !self.hard_cap.is_zero() && self.total_raised >= self.hard_cap
+    }
+    
+    pub fn progress_percent(&self) -> f64 {
+        if self.hard_cap.is_zero() {
+            return 0.0;
+        }
+        // Basis points keep the division in integers; U256 is too wide for f64
+        let basis_points = self.total_raised.saturating_mul(U256::from(10_000)) / self.hard_cap;
+        basis_points.min(U256::from(u64::MAX)).as_u64() as f64 / 100.0
+    }
+    
+    pub fn time_remaining(&self, now: u64) -> u64 {
+        let end_time = self.end_time.min(U256::from(u64::MAX)).as_u64();
+        end_time.saturating_sub(now)
+    }
+    
+    pub fn metrics(&self, now: u64) -> ProjectMetrics {
+        ProjectMetrics {
+            phase: self.phase(now),
+            progress_percent: self.progress_percent(),
+            time_remaining_secs: self.time_remaining(now),
+            soft_cap_reached: self.soft_cap_reached(),
+            hard_cap_reached: self.hard_cap_reached(),
+        }
+    }
+}
+
+/// A launchpad event decoded from a transaction receipt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum LaunchpadEvent {
+    ProjectCreated {
+        project: Address,
+        creator: Address,
+        name: String,
+    },
+    Invested {
+        project: Address,
+        investor: Address,
+        amount: U256,
+        token_amount: U256,
+        timestamp: U256,
+        tier: u8,
+    },
+    ProjectStatusChanged {
+        project: Address,
+        status: u8,
+    },
+    /// `amount` is the token amount sent to the investor.
+    TokensClaimed {
+        project: Address,
+        investor: Address,
+        amount: U256,
+    },
+    /// `amount` is the wei returned to the investor.
+    RefundClaimed {
+        project: Address,
+        investor: Address,
+        amount: U256,
+    },
+}
+
+/// Multicall3's address on nearly every EVM chain, BSC and its testnet included.
+pub const DEFAULT_MULTICALL_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+/// `getProject` calls per `aggregate3`, keeping each `eth_call` under node gas caps.
+const MULTICALL_BATCH_SIZE: usize = 100;
+
+type GetProjectOutput = (Address, Address, String, String, U256, U256, U256, U256, U256, u8);
+
+/// A project that could not be loaded, and why.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFetchError {
+    pub address: Address,
+    pub reason: String,
+}
+
+/// Projects loaded in bulk, in request order, with the ones that failed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectBatch {
+    pub projects: Vec<Project>,
+    pub failures: Vec<ProjectFetchError>,
 }
 
 #[derive(Debug, Clone)]
 pub struct LaunchpadClient {
-   pub client: Arc<SignerMiddleware<Provider<Http>, LocalWallet>>,
-   pub contract: MechaLaunchpad<SignerMiddleware<Provider<Http>, LocalWallet>>,
+   pub client: Arc<SignerMiddleware<RpcProvider, LocalWallet>>,
+   pub contract: MechaLaunchpad<SignerMiddleware<RpcProvider, LocalWallet>>,
+   pub multicall: Address,
 }
 
 impl LaunchpadClient {
@@ -91,15 +375,16 @@ impl LaunchpadClient {
         private_key: &str,
         chain_id: u64,
      ) -> Result<Self> {
-        let provider = Provider::<Http>::try_from(provider_url)?;
+        let provider = FailoverClient::new(&[provider_url])?.provider();
         
         let wallet_result: Result<LocalWallet, _> = private_key.parse();
         let wallet = wallet_result?.with_chain_id(chain_id);
         
         let client = Arc::new(SignerMiddleware::new(provider, wallet));
         let contract = MechaLaunchpad::new(contract_address, client.clone());
+        let multicall = DEFAULT_MULTICALL_ADDRESS.parse()?;
         
-        Ok(Self { client, contract })
+        Ok(Self { client, contract, multicall })
     }
     
     pub async fn register_user(&self, telegram_id: u64, telegram_username: String) -> Result<TransactionReceipt> {
@@ -135,28 +420,70 @@ impl LaunchpadClient {
         .get_project(project_id)
         .call()
         .await?;
-     let (
-        creator,
-        offering_token,
-        name,
-        symbol,
-        soft_cap,
-        hard_cap,
-        total_raised,
-        start_time,
-        end_time,
-        status
-     ) = result;
-
-     Ok(Project {
-        creator,
-        name: name.to_string(),
-        symbol: symbol.to_string(),
-        decimal: 9,
-        initial_supply: U256::zero(),
-      })
+     project_from_output(project_id, result)
+    }
+    
+    /// `getProject` as answered identically by at least `quorum` of `rpc`'s
+    /// endpoints, all read at the lowest head among them.
+    pub async fn get_project_quorum(&self, project_id: Address, rpc: &FailoverClient, quorum: usize) -> Result<Project> {
+        let call = self.contract.get_project(project_id);
+        let block = rpc.lowest_head().await?;
+        let output: Bytes = rpc
+            .quorum_request("eth_call", (&call.tx, BlockNumber::Number(block)), quorum)
+            .await?;
+        let tokens = call.function.decode_output(&output)?;
+        project_from_output(project_id, GetProjectOutput::from_tokens(tokens)?)
+    }
+    
+    /// Loads many projects through Multicall3, a batch of calls per `eth_call`.
+    /// Falls back to one call per project when the multicall itself fails
+    /// (e.g. no Multicall3 on this chain).
+    pub async fn get_projects_batched(&self, project_addresses: &[Address]) -> ProjectBatch {
+        let mut batch = ProjectBatch::default();
+        
+        for chunk in project_addresses.chunks(MULTICALL_BATCH_SIZE) {
+            match self.multicall_projects(chunk).await {
+                Ok(results) => {
+                    for (address, result) in chunk.iter().zip(results) {
+                        match result {
+                            Ok(project) => batch.projects.push(project),
+                            Err(reason) => batch.failures.push(ProjectFetchError { address: *address, reason }),
+                        }
+                    }
+                }
+                Err(e) => {
+                    println!("⚠️ Multicall at {:?} failed ({}), loading {} project(s) one by one", self.multicall, e, chunk.len());
+                    for address in chunk {
+                        match self.get_project_details(*address).await {
+                            Ok(project) => batch.projects.push(project),
+                            Err(e) => batch.failures.push(ProjectFetchError { address: *address, reason: e.to_string() }),
+                        }
+                    }
+                }
+            }
+        }
+        
+        batch
     }
+    
+    async fn multicall_projects(&self, project_addresses: &[Address]) -> Result<Vec<std::result::Result<Project, String>>> {
+        let mut multicall = Multicall::new(self.client.clone(), Some(self.multicall)).await?;
+        for address in project_addresses {
+            multicall.add_call(self.contract.get_project(*address), true);
+        }
         
+        let results = multicall.call_raw().await?;
+        Ok(project_addresses
+            .iter()
+            .zip(results)
+            .map(|(address, result)| {
+                let token = result.map_err(|revert| decode_revert(&revert))?;
+                let output = GetProjectOutput::from_token(token).map_err(|e| e.to_string())?;
+                project_from_output(*address, output).map_err(|e| e.to_string())
+            })
+            .collect())
+    }
+    
     pub async fn get_all_projects(&self) -> Result<Vec<Address>> {
         let projects = self.contract
             .get_all_projects()
@@ -203,11 +530,11 @@ impl LaunchpadClient {
             .call()
             .await?;
         Ok((
-            stats.0.as_u64(),
-            stats.1.as_u64(),
-            stats.2.as_u64(),
+            saturating_u64(stats.0),
+            saturating_u64(stats.1),
+            saturating_u64(stats.2),
             stats.3,
-            stats.4.as_u64(),
+            saturating_u64(stats.4),
         ))
     }
     
@@ -216,61 +543,21 @@ impl LaunchpadClient {
      self.get_project_details(project_id).await
     }
     
-    pub async fn get_project_marketing_info(&self, project_id: Address) -> Result<(U256, Address, U256, bool, U256)> {
-        let info = self.contract
+    pub async fn get_project_marketing_info(&self, project_id: Address) -> Result<MarketingInfo> {
+        let result = self.contract
             .get_project_marketing_info(project_id)
             .call()
             .await?;
-        Ok(info)
-    }
-    
-    pub async fn create_project(
-     &self,
-     creator: Address,
-     token_name: String,
-     token_symbol: String,
-     token_decimals: u8,
-     initial_supply: U256,
-    ) -> Result<Address> {
-     let create_call = self.contract.create_project_with_token_via_telegram(
-            creator,
-            token_name,
-            token_symbol,
-            token_decimals,
-            initial_supply
-        );
-
-        let gas_estimate = create_call.estimate_gas().await.unwrap_or(U256::from(300_000_000));
-        println!("Estimated Gas {:?}", gas_estimate);
-        let gas_with_buffer = gas_estimate * U256::from(120) / U256::from(100);
-        
-        let gas_call = create_call.gas(gas_with_buffer);
-        
-        // Estimate and set appropriate gas price
-        let gas_price = self.client.get_gas_price().await?;
-        let gas_call_with_price = gas_call.gas_price(gas_price);
         
-        let tx = gas_call_with_price.send().await.map_err(|e| {
-            anyhow::anyhow!("Send failed: {:?}", e)
-        })?;
-        
-        let receipt = tx.await?.ok_or_else(|| anyhow::anyhow!("Transaction failed - no receipt"))?;
-        
-        let logs = receipt.logs;
-        for log in logs {
-            if let Ok(decoded) = self.contract.decode_event::<(Address, Address, U256, String)>(
-                "ProjectCreated",
-                log.topics.clone(),
-                log.data.clone(),
-            ) {
-                let (project_address, project_creator, _, _) = decoded;
-                println!("Project created at: {:?} by: {:?}", project_address, project_creator);
-                return Ok(project_address);
-            }
-        }
-        
-        Err(anyhow::anyhow!("Failed to parse project address from transaction receipt"))
+        Ok(MarketingInfo {
+            allocation: result.0,
+            wallet: result.1,
+            released: result.2,
+            is_active: result.3,
+            last_updated: result.4,
+        })
     }
+    
     pub async fn invest(&self, project_id: Address, amount: U256) -> Result<TransactionReceipt> {
      let call = self.contract.invest(project_id).value(amount);
      let tx = call.send().await?;
@@ -303,81 +590,126 @@ impl LaunchpadClient {
      receipt.ok_or_else(|| anyhow::anyhow!("Transaction failed"))
     }
         
-    pub async fn get_projects_with_details(&self, project_addresses: Vec<Address>) -> Result<Vec<Project>> {
-        let mut projects = Vec::new();
-        
-        for address in project_addresses {
-            if let Ok(project) = self.get_project(address).await {
-                projects.push(project);
-            }
-        }
-        
-        Ok(projects)
-    }
-    
     pub async fn get_projects_summary(&self, project_addresses: Vec<Address>) -> Result<Vec<ProjectSummary>> {
-        let mut summaries = Vec::new();
+        let batch = self.get_projects_batched(&project_addresses).await;
         
-        for address in project_addresses {
-            if let Ok(project) = self.get_project(address).await {
-                let progress = if project.initial_supply > U256::zero() {
-                    (project.initial_supply * U256::from(100)) / project.initial_supply
-                } else {
-                    U256::zero()
-                };
-                
-                summaries.push(ProjectSummary {
-                    id: project.creator,
-                    name: project.name,
-                    total_raised: project.initial_supply,
-                    hard_cap: project.initial_supply,
-                    progress: progress.as_u64(),
-                });
-            }
-        }
+        let summaries = batch.projects
+            .into_iter()
+            .map(|project| ProjectSummary {
+                id: project.address,
+                progress: project.progress_percent(),
+                name: project.name,
+                total_raised: project.total_raised,
+                hard_cap: project.hard_cap,
+                status: project.status,
+            })
+            .collect();
         
         Ok(summaries)
-    }    
-    pub async fn listen_project_created(&self) -> Result<()> {
-        let filter = Filter::new()
-            .address(ValueOrArray::Value(self.contract.address()))
-            .event("ProjectCreated");
-            
-        let mut stream = self.client.watch(&filter).await?;
-        
-        while let Some(log) = stream.next().await {
-      //      println!("New project created: {:?}", log);
-        }
-        
-        Ok(())
     }
     
-    pub async fn listen_investments(&self) -> Result<()> {
-        let filter = Filter::new()
-            .address(ValueOrArray::Value(self.contract.address()))
-            .event("Invested");
-            
-        let mut stream = self.client.watch(&filter).await?;
-        
-        while let Some(_log) = stream.next().await {
-            println!("New investment: {:?}", _log);
-        }
-        
-        Ok(())
+    // Calldata for transactions the user signs in their own wallet
+    
+    pub fn invest_calldata(&self, project_id: Address) -> Result<Bytes> {
+        self.contract
+            .invest(project_id)
+            .calldata()
+            .ok_or_else(|| anyhow::anyhow!("Failed to encode invest calldata"))
     }
     
-    pub async fn listen_user_registrations(&self) -> Result<()> {
-        let filter = Filter::new()
-            .address(ValueOrArray::Value(self.contract.address()))
-            .event("UserRegistered");
-            
-        let mut stream = self.client.watch(&filter).await?;
+    pub fn claim_tokens_calldata(&self, project_id: Address) -> Result<Bytes> {
+        self.contract
+            .claim_tokens(project_id)
+            .calldata()
+            .ok_or_else(|| anyhow::anyhow!("Failed to encode claimTokens calldata"))
+    }
+    
+    pub fn complete_project_calldata(&self, project_id: Address) -> Result<Bytes> {
+        self.contract
+            .complete_project(project_id)
+            .calldata()
+            .ok_or_else(|| anyhow::anyhow!("Failed to encode completeProject calldata"))
+    }
+    
+    pub fn claim_refund_calldata(&self, project_id: Address) -> Result<Bytes> {
+        self.contract
+            .claim_refund(project_id)
+            .calldata()
+            .ok_or_else(|| anyhow::anyhow!("Failed to encode claimRefund calldata"))
+    }
+    
+    pub fn create_project_calldata(
+        &self,
+        creator: Address,
+        token_name: String,
+        token_symbol: String,
+        token_decimals: u8,
+        initial_supply: U256,
+    ) -> Result<Bytes> {
+        self.contract
+            .create_project_with_token_via_telegram(creator, token_name, token_symbol, token_decimals, initial_supply)
+            .calldata()
+            .ok_or_else(|| anyhow::anyhow!("Failed to encode createProjectWithTokenViaTelegram calldata"))
+    }
+    
+    /// Decodes the launchpad's own events from receipt logs, skipping logs
+    /// emitted by other contracts (e.g. the new token's `Transfer`).
+    pub fn decode_events(&self, logs: &[Log]) -> Vec<LaunchpadEvent> {
+        logs.iter()
+            .filter(|log| log.address == self.contract.address())
+            .filter_map(|log| self.decode_event(log))
+            .collect()
+    }
+    
+    // The ABI's event parameters are unnamed, which ethabi's `parse_log` cannot
+    // line up, so indexed topics and data are decoded by position here.
+    fn decode_event(&self, log: &Log) -> Option<LaunchpadEvent> {
+        let signature = *log.topics.first()?;
+        let event = self.contract.abi().events().find(|event| event.signature() == signature)?;
+        let indexed: Vec<Address> = log.topics[1..].iter().map(|topic| Address::from(*topic)).collect();
         
-        while let Some(log) = stream.next().await {
-            println!("New user registered: {:?}", log);
+        match event.name.as_str() {
+            "ProjectCreated" => {
+                let data = decode(&[ParamType::Uint(256), ParamType::String], &log.data).ok()?;
+                Some(LaunchpadEvent::ProjectCreated {
+                    project: *indexed.first()?,
+                    creator: *indexed.get(1)?,
+                    name: data.get(1)?.clone().into_string()?,
+                })
+            }
+            "Invested" => {
+                let data = decode(
+                    &[ParamType::Uint(256), ParamType::Uint(256), ParamType::Uint(256), ParamType::Uint(8)],
+                    &log.data,
+                ).ok()?;
+                Some(LaunchpadEvent::Invested {
+                    project: *indexed.first()?,
+                    investor: *indexed.get(1)?,
+                    amount: data.first()?.clone().into_uint()?,
+                    token_amount: data.get(1)?.clone().into_uint()?,
+                    timestamp: data.get(2)?.clone().into_uint()?,
+                    tier: data.get(3)?.clone().into_uint()?.low_u32() as u8,
+                })
+            }
+            "ProjectStatusChanged" => {
+                let data = decode(&[ParamType::Uint(8)], &log.data).ok()?;
+                Some(LaunchpadEvent::ProjectStatusChanged {
+                    project: *indexed.first()?,
+                    status: data.first()?.clone().into_uint()?.low_u32() as u8,
+                })
+            }
+            "TokensClaimed" | "RefundClaimed" => {
+                let data = decode(&[ParamType::Uint(256)], &log.data).ok()?;
+                let (project, investor) = (*indexed.first()?, *indexed.get(1)?);
+                let amount = data.first()?.clone().into_uint()?;
+                Some(if event.name == "TokensClaimed" {
+                    LaunchpadEvent::TokensClaimed { project, investor, amount }
+                } else {
+                    LaunchpadEvent::RefundClaimed { project, investor, amount }
+                })
+            }
+            _ => None,
         }
-        
-        Ok(())
     }
     
     // Utility Functions
@@ -386,3 +718,120 @@ impl LaunchpadClient {
         self.contract.address()
     }
 }
+
+fn project_from_output(address: Address, output: GetProjectOutput) -> Result<Project> {
+    let (creator, offering_token, name, symbol, soft_cap, hard_cap, total_raised, start_time, end_time, status) = output;
+    let status = ProjectStatus::from_u8(status)
+        .ok_or_else(|| anyhow::anyhow!("Unknown status {} for project {:?}", status, address))?;
+    
+    Ok(Project {
+        address,
+        creator,
+        offering_token,
+        name,
+        symbol,
+        soft_cap,
+        hard_cap,
+        total_raised,
+        start_time,
+        end_time,
+        status,
+    })
+}
+
+/// A count read from the contract; one past `u64::MAX` cannot happen in
+/// practice, but must not panic either.
+fn saturating_u64(value: U256) -> u64 {
+    value.min(U256::from(u64::MAX)).as_u64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    /// A raise with a 10/100 wei soft/hard cap that opened an hour ago and
+    /// closes in an hour.
+    fn project(status: ProjectStatus, total_raised: u64) -> Project {
+        Project {
+            address: Address::repeat_byte(0x11),
+            creator: Address::repeat_byte(0x22),
+            offering_token: Address::repeat_byte(0x33),
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            soft_cap: U256::from(10),
+            hard_cap: U256::from(100),
+            total_raised: U256::from(total_raised),
+            start_time: U256::from(NOW - 3600),
+            end_time: U256::from(NOW + 3600),
+            status,
+        }
+    }
+
+    #[test]
+    fn zero_marketing_record_is_empty() {
+        let mut info = MarketingInfo {
+            allocation: U256::zero(),
+            wallet: Address::zero(),
+            released: U256::zero(),
+            is_active: false,
+            last_updated: U256::zero(),
+        };
+        assert!(info.is_empty());
+        info.allocation = U256::from(1);
+        assert!(!info.is_empty());
+    }
+
+    #[test]
+    fn invest_requires_active_status() {
+        assert!(project(ProjectStatus::Active, 0).invest_refusal(NOW).is_none());
+        for status in [ProjectStatus::Pending, ProjectStatus::Approved, ProjectStatus::Rejected, ProjectStatus::Completed] {
+            assert!(project(status, 0).invest_refusal(NOW).is_some(), "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn invest_refused_after_hard_cap() {
+        assert!(project(ProjectStatus::Active, 100).invest_refusal(NOW).is_some());
+    }
+
+    #[test]
+    fn refund_follows_phase_while_active() {
+        let mut ended_short = project(ProjectStatus::Active, 5);
+        ended_short.end_time = U256::from(NOW - 1);
+        assert!(ended_short.refund_refusal(NOW).is_none());
+
+        assert!(project(ProjectStatus::Active, 5).refund_refusal(NOW).is_some());
+
+        let mut ended_funded = project(ProjectStatus::Active, 50);
+        ended_funded.end_time = U256::from(NOW - 1);
+        assert!(ended_funded.refund_refusal(NOW).is_some());
+    }
+
+    #[test]
+    fn refund_follows_admin_status() {
+        // Marked failed mid-sale: refunds open even though the window has not closed
+        assert!(project(ProjectStatus::Failed, 50).refund_refusal(NOW).is_none());
+        for status in [ProjectStatus::Pending, ProjectStatus::Rejected, ProjectStatus::Successful, ProjectStatus::Completed] {
+            let mut ended_short = project(status, 5);
+            ended_short.end_time = U256::from(NOW - 1);
+            assert!(ended_short.refund_refusal(NOW).is_some(), "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn complete_refused_once_completed() {
+        let completed = project(ProjectStatus::Completed, 100);
+        assert_eq!(completed.complete_refusal(NOW), Some("This project has already been completed."));
+    }
+
+    #[test]
+    fn complete_follows_status_and_phase() {
+        assert!(project(ProjectStatus::Active, 100).complete_refusal(NOW).is_none());
+        assert!(project(ProjectStatus::Successful, 50).complete_refusal(NOW).is_none());
+        assert!(project(ProjectStatus::Active, 50).complete_refusal(NOW).is_some());
+        assert!(project(ProjectStatus::Failed, 100).complete_refusal(NOW).is_some());
+        assert!(project(ProjectStatus::Pending, 100).complete_refusal(NOW).is_some());
+    }
+}
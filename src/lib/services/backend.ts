@@ -20,6 +20,68 @@ export interface HelloResponse {
     timestamp: string;
 }
 
+export type IntentStage =
+    | 'parsing'
+    | 'parsed'
+    | 'executing'
+    | 'tx_prepared'
+    | 'token'
+    | 'completed'
+    | 'failed';
+
+export interface IntentStreamEvent {
+    stage: IntentStage;
+    [key: string]: any;
+}
+
+export interface IntentRecord {
+    intent_id: string;
+    state: 'parsed' | 'prepared' | 'awaiting_signature' | 'submitted' | 'confirmed' | 'failed' | 'expired' | 'superseded';
+    tx_hash?: string;
+    receipt?: {
+        status: number;
+        from: string;
+        block_number: number;
+        confirmations: number;
+        gas_used?: string;
+        events: Array<{ event: string; [key: string]: any }>;
+    };
+    [key: string]: any;
+}
+
+export interface Investment {
+    investor: string;
+    project_id: string;
+    amount: string;
+    token_amount: string;
+    timestamp: string;
+    refunded: boolean;
+    tokens_claimed: boolean;
+}
+
+export interface ChainInfo {
+    chain_id: number;
+    name: string;
+    native_symbol: string;
+    launchpad_address?: string;
+    multicall_address: string;
+    explorer_url: string;
+    confirmations: number;
+    rpc_quorum: number;
+    // Unset when detected from the chain's blocks
+    fee_model?: 'eip1559' | 'legacy' | null;
+    active: boolean;
+    event_transport?: 'web_socket' | 'http_polling';
+    rpc_endpoints?: {
+        host: string;
+        healthy: boolean;
+        latency_ms?: number;
+        requests: number;
+        failures: number;
+        consecutive_failures: number;
+    }[];
+}
+
 export const backendService = {
     // Test connection
     async sayHello(name: string): Promise<HelloResponse> {
@@ -27,6 +89,74 @@ export const backendService = {
         return response.data;
     },
     
+    // Stream intent pipeline stages and AI tokens over Server-Sent Events.
+    // Returns a function that closes the stream.
+    streamIntent(userInput: string, onEvent: (event: IntentStreamEvent) => void, userId?: number): () => void {
+        const params = new URLSearchParams({ user_input: userInput });
+        if (userId !== undefined) params.set('user_id', String(userId));
+        
+        const source = new EventSource(`${BACKEND_URL}/api/intents/stream?${params}`);
+        const stages: IntentStage[] = ['parsing', 'parsed', 'executing', 'tx_prepared', 'token', 'completed', 'failed'];
+        
+        for (const stage of stages) {
+            source.addEventListener(stage, (e) => {
+                onEvent(JSON.parse((e as MessageEvent).data));
+                if (stage === 'completed' || stage === 'failed') source.close();
+            });
+        }
+        source.onerror = () => source.close();
+        
+        return () => source.close();
+    },
+    
+    // Report the hash of a transaction the wallet broadcast, so the backend
+    // can watch for its receipt.
+    async reportSubmitted(intentId: string, txHash: string): Promise<boolean> {
+        const response = await api.post(`/api/intents/${intentId}/submitted`, { tx_hash: txHash });
+        return response.data.success;
+    },
+    
+    // Follow an intent's record until it is confirmed, failed or expired.
+    // Returns a function that closes the stream.
+    watchIntent(intentId: string, onUpdate: (intent: IntentRecord) => void): () => void {
+        const source = new EventSource(`${BACKEND_URL}/api/intents/${intentId}/events`);
+        
+        source.addEventListener('intent', (e) => {
+            const intent: IntentRecord = JSON.parse((e as MessageEvent).data);
+            onUpdate(intent);
+            if (['confirmed', 'failed', 'expired'].includes(intent.state)) source.close();
+        });
+        source.addEventListener('failed', () => source.close());
+        source.onerror = () => source.close();
+        
+        return () => source.close();
+    },
+    
+    // Investments made by an address, or by the connected wallet when none is given.
+    async getInvestments(address?: string): Promise<Investment[]> {
+        const response = await api.get('/api/wallet/investments', { params: address ? { address } : {} });
+        if (!response.data.success) throw new Error(response.data.message);
+        return response.data.investments;
+    },
+    
+    // Launchpad events as they are mined, before they are final.
+    // Returns a function that closes the stream.
+    watchLaunchpadEvents(onEvent: (event: { [key: string]: any }) => void, chainId?: number): () => void {
+        const params = chainId !== undefined ? `?chain_id=${chainId}` : '';
+        const source = new EventSource(`${BACKEND_URL}/api/launchpad/events${params}`);
+        
+        source.addEventListener('launchpad', (e) => onEvent(JSON.parse((e as MessageEvent).data)));
+        source.addEventListener('failed', () => source.close());
+        
+        return () => source.close();
+    },
+    
+    // Chains the backend knows, and which of them the agent serves.
+    async getChains(): Promise<{ default_chain_id: number; chains: ChainInfo[] }> {
+        const response = await api.get('/api/chains');
+        return response.data;
+    },
+    
     // Check if backend is running
     async checkConnection(): Promise<boolean> {
         try {
@@ -1,25 +1,99 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 
-#[derive(Debug, Clone)]
-pub struct DeepSeekClient {
-    api_key: String,
-    client: Client,
+pub const DEFAULT_SYSTEM_PROMPT: &str = "You are Teemah AI, a helpful Web3 assistant that helps users with cryptocurrency projects, investments, and blockchain transactions. Provide clear, accurate, and friendly responses.";
+
+const DEEPSEEK_BASE_URL: &str = "https://api.deepseek.com/v1";
+const DEEPSEEK_MODEL: &str = "deepseek-chat";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
+
+impl Message {
+    pub fn system(content: &str) -> Self {
+        Self { role: "system".to_string(), content: content.to_string() }
+    }
+
+    pub fn user(content: &str) -> Self {
+        Self { role: "user".to_string(), content: content.to_string() }
+    }
+
+    pub fn assistant(content: &str) -> Self {
+        Self { role: "assistant".to_string(), content: content.to_string() }
+    }
+}
+
+/// OpenAI-style `tools` entry describing one callable function.
+#[derive(Serialize, Debug, Clone)]
+pub struct ToolDefinition {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: FunctionDefinition,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FunctionDefinition {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+impl ToolDefinition {
+    pub fn function(name: &str, description: &str, parameters: serde_json::Value) -> Self {
+        Self {
+            kind: "function".to_string(),
+            function: FunctionDefinition {
+                name: name.to_string(),
+                description: description.to_string(),
+                parameters,
+            },
+        }
+    }
+}
+
+/// A function call requested by the model. `arguments` is the raw JSON
+/// string exactly as the model produced it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ToolCall {
+    #[serde(default)]
+    pub id: String,
+    pub function: FunctionCall,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FunctionCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct Message {
-    role: String,
-    content: String,
+/// The assistant turn returned by a tool-enabled completion.
+#[derive(Debug, Clone, Default)]
+pub struct ChatCompletion {
+    pub content: Option<String>,
+    pub tool_calls: Vec<ToolCall>,
 }
 
 #[derive(Serialize, Debug)]
-struct ChatRequest {
+struct ChatRequest<'a> {
     model: String,
     messages: Vec<Message>,
     temperature: f32,
     max_tokens: u32,
+    #[serde(skip_serializing_if = "<[ToolDefinition]>::is_empty")]
+    tools: &'a [ToolDefinition],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<&'static str>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -29,13 +103,126 @@ struct ChatResponse {
 
 #[derive(Deserialize, Debug)]
 struct Choice {
-    message: Message,
+    message: ResponseMessage,
 }
 
-impl DeepSeekClient {
-    pub fn new(api_key: &str) -> Self {
+#[derive(Deserialize, Debug)]
+struct ResponseMessage {
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCall>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamChunk {
+    choices: Vec<StreamChoice>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamChoice {
+    delta: StreamDelta,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamDelta {
+    content: Option<String>,
+}
+
+/// Incremental text deltas from a streaming completion.
+pub type TokenStream = BoxStream<'static, Result<String>>;
+
+/// A chat-completion backend the agent can talk to.
+#[async_trait]
+pub trait LlmProvider: std::fmt::Debug + Send + Sync {
+    /// Short identifier used in logs and status responses.
+    fn name(&self) -> &str;
+
+    /// Sends a full message list and returns the assistant's reply.
+    async fn chat(&self, messages: Vec<Message>) -> Result<String>;
+
+    /// Sends a message list with `tools` available and returns either the
+    /// model's tool calls or its plain-text answer.
+    async fn chat_with_tools(&self, messages: Vec<Message>, tools: &[ToolDefinition]) -> Result<ChatCompletion>;
+
+    /// Streams the assistant's reply as text deltas. Providers without native
+    /// streaming yield the whole reply as a single chunk.
+    async fn chat_stream(&self, messages: Vec<Message>) -> Result<TokenStream> {
+        let reply = self.chat(messages).await?;
+        Ok(futures::stream::once(async move { Ok(reply) }).boxed())
+    }
+}
+
+/// Which LLM backend to build, as sent to `/api/agent/initialize`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "provider", rename_all = "snake_case")]
+pub enum LlmConfig {
+    DeepSeek {
+        api_key: String,
+        model: Option<String>,
+    },
+    OpenAiCompatible {
+        base_url: String,
+        api_key: Option<String>,
+        model: String,
+        temperature: Option<f32>,
+        max_tokens: Option<u32>,
+    },
+    Mock {
+        #[serde(default)]
+        responses: Vec<String>,
+    },
+}
+
+impl LlmConfig {
+    pub fn build(&self) -> Result<Arc<dyn LlmProvider>> {
+        match self {
+            LlmConfig::DeepSeek { api_key, model } => {
+                if api_key.trim().is_empty() {
+                    return Err(anyhow!("DeepSeek provider requires an API key"));
+                }
+                let mut client = DeepSeekClient::new(api_key);
+                if let Some(model) = model {
+                    client.inner.model = model.clone();
+                }
+                Ok(Arc::new(client))
+            }
+            LlmConfig::OpenAiCompatible { base_url, api_key, model, temperature, max_tokens } => {
+                let mut client = OpenAiCompatibleClient::new(base_url, api_key.as_deref(), model);
+                if let Some(temperature) = temperature {
+                    client.temperature = *temperature;
+                }
+                if let Some(max_tokens) = max_tokens {
+                    client.max_tokens = *max_tokens;
+                }
+                Ok(Arc::new(client))
+            }
+            LlmConfig::Mock { responses } => {
+                Ok(Arc::new(MockLlmClient::new(responses.clone())))
+            }
+        }
+    }
+}
+
+/// Client for any server speaking the OpenAI `/chat/completions` protocol
+/// (vLLM, llama.cpp server, Ollama, DeepSeek, ...).
+#[derive(Debug, Clone)]
+pub struct OpenAiCompatibleClient {
+    base_url: String,
+    api_key: Option<String>,
+    pub model: String,
+    pub temperature: f32,
+    pub max_tokens: u32,
+    client: Client,
+}
+
+impl OpenAiCompatibleClient {
+    pub fn new(base_url: &str, api_key: Option<&str>, model: &str) -> Self {
         Self {
-            api_key: api_key.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.filter(|k| !k.is_empty()).map(|k| k.to_string()),
+            model: model.to_string(),
+            temperature: 0.7,
+            max_tokens: 500,
             client: Client::builder()
                 .timeout(std::time::Duration::from_secs(70))
                 .build()
@@ -43,29 +230,19 @@ impl DeepSeekClient {
         }
     }
 
-    pub async fn generate_response(&self, prompt: &str) -> Result<String> {
-        let messages = vec![
-            Message {
-                role: "system".to_string(),
-                content: "You are Teemah AI, a helpful Web3 assistant that helps users with cryptocurrency projects, investments, and blockchain transactions. Provide clear, accurate, and friendly responses.".to_string(),
-            },
-            Message {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            },
-        ];
+    fn completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url)
+    }
 
-        let request = ChatRequest {
-            model: "deepseek-chat".to_string(),
-            messages,
-            temperature: 0.7,
-            max_tokens: 500,
-        };
+    async fn send(&self, request: &ChatRequest<'_>) -> Result<reqwest::Response> {
+        let mut builder = self.client
+            .post(self.completions_url())
+            .json(request);
+        if let Some(api_key) = &self.api_key {
+            builder = builder.header("Authorization", format!("Bearer {}", api_key));
+        }
 
-        let response = self.client
-            .post("https://api.deepseek.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&request)
+        let response = builder
             .send()
             .await
             .map_err(|e| anyhow!("HTTP request failed: {}", e))?;
@@ -77,12 +254,309 @@ impl DeepSeekClient {
             return Err(anyhow!("API request failed with status {}: {}", status, body));
         }
 
+        Ok(response)
+    }
+
+    async fn complete(&self, messages: Vec<Message>, tools: &[ToolDefinition]) -> Result<ResponseMessage> {
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages,
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            tools,
+            tool_choice: if tools.is_empty() { None } else { Some("auto") },
+            stream: false,
+        };
+
+        let response = self.send(&request).await?;
         let parsed: ChatResponse = response.json().await
             .map_err(|e| anyhow!("Failed to parse JSON response: {}", e))?;
-        
+
         parsed.choices
-            .first()
-            .map(|choice| choice.message.content.clone())
+            .into_iter()
+            .next()
+            .map(|choice| choice.message)
             .ok_or_else(|| anyhow!("No choices returned in API response"))
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiCompatibleClient {
+    fn name(&self) -> &str {
+        "openai_compatible"
+    }
+
+    async fn chat(&self, messages: Vec<Message>) -> Result<String> {
+        let message = self.complete(messages, &[]).await?;
+        message.content.ok_or_else(|| anyhow!("API response contained no message content"))
+    }
+
+    async fn chat_with_tools(&self, messages: Vec<Message>, tools: &[ToolDefinition]) -> Result<ChatCompletion> {
+        let message = self.complete(messages, tools).await?;
+        Ok(ChatCompletion {
+            content: message.content,
+            tool_calls: message.tool_calls,
+        })
+    }
+
+    async fn chat_stream(&self, messages: Vec<Message>) -> Result<TokenStream> {
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages,
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            tools: &[],
+            tool_choice: None,
+            stream: true,
+        };
+        let response = self.send(&request).await?;
+
+        let (tx, rx) = futures::channel::mpsc::unbounded::<Result<String>>();
+        tokio::spawn(async move {
+            let mut body = response.bytes_stream();
+            let mut buffer: Vec<u8> = Vec::new();
+
+            loop {
+                let chunk = body.next().await;
+                let at_end = chunk.is_none();
+                match chunk {
+                    Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                    Some(Err(e)) => {
+                        let _ = tx.unbounded_send(Err(anyhow!("Stream read failed: {}", e)));
+                        return;
+                    }
+                    None => {}
+                }
+
+                while let Some(line) = next_sse_line(&mut buffer, at_end) {
+                    match decode_sse_line(&line) {
+                        Ok(SseLine::Delta(delta)) => {
+                            if tx.unbounded_send(Ok(delta)).is_err() {
+                                return;
+                            }
+                        }
+                        Ok(SseLine::Skip) => {}
+                        Ok(SseLine::Done) => return,
+                        Err(e) => {
+                            let _ = tx.unbounded_send(Err(e));
+                            return;
+                        }
+                    }
+                }
+                if at_end {
+                    return;
+                }
+            }
+        });
+
+        Ok(rx.boxed())
+    }
+}
+
+/// One decoded server-sent-events line of a streaming completion.
+#[derive(Debug, PartialEq)]
+enum SseLine {
+    Delta(String),
+    Skip,
+    Done,
+}
+
+/// Takes the next whole line off `buffer`. Server-sent events arrive as
+/// `data: {...}` lines and a chunk may end mid-line, or mid-character, so
+/// the tail waits for the next read; once the body has ended it is flushed
+/// as the final line.
+fn next_sse_line(buffer: &mut Vec<u8>, at_end: bool) -> Option<Vec<u8>> {
+    match buffer.iter().position(|b| *b == b'\n') {
+        Some(newline) => Some(buffer.drain(..=newline).collect()),
+        None if at_end && !buffer.is_empty() => Some(std::mem::take(buffer)),
+        None => None,
+    }
+}
+
+fn decode_sse_line(bytes: &[u8]) -> Result<SseLine> {
+    let line = String::from_utf8_lossy(bytes);
+    let Some(payload) = line.trim().strip_prefix("data:") else {
+        return Ok(SseLine::Skip);
+    };
+    let payload = payload.trim();
+    if payload == "[DONE]" {
+        return Ok(SseLine::Done);
+    }
+    let parsed = serde_json::from_str::<StreamChunk>(payload)
+        .map_err(|e| anyhow!("Failed to parse stream chunk: {}", e))?;
+    let delta = parsed.choices
+        .into_iter()
+        .filter_map(|choice| choice.delta.content)
+        .collect::<String>();
+    Ok(if delta.is_empty() { SseLine::Skip } else { SseLine::Delta(delta) })
+}
+
+/// DeepSeek's hosted API with its default model and sampling settings.
+#[derive(Debug, Clone)]
+pub struct DeepSeekClient {
+    inner: OpenAiCompatibleClient,
+}
+
+impl DeepSeekClient {
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            inner: OpenAiCompatibleClient::new(DEEPSEEK_BASE_URL, Some(api_key), DEEPSEEK_MODEL),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for DeepSeekClient {
+    fn name(&self) -> &str {
+        "deepseek"
+    }
+
+    async fn chat(&self, messages: Vec<Message>) -> Result<String> {
+        self.inner.chat(messages).await
+    }
+
+    async fn chat_with_tools(&self, messages: Vec<Message>, tools: &[ToolDefinition]) -> Result<ChatCompletion> {
+        self.inner.chat_with_tools(messages, tools).await
+    }
+
+    async fn chat_stream(&self, messages: Vec<Message>) -> Result<TokenStream> {
+        self.inner.chat_stream(messages).await
+    }
+}
+
+/// In-process provider that replays scripted replies in order, for running
+/// the intent pipeline without a network. In `chat_with_tools`, a scripted
+/// reply shaped like `{"name": ..., "arguments": {...}}` is returned as a
+/// tool call; anything else is returned as plain content.
+#[derive(Debug)]
+pub struct MockLlmClient {
+    responses: Mutex<VecDeque<String>>,
+}
+
+impl MockLlmClient {
+    pub fn new(responses: Vec<String>) -> Self {
+        Self {
+            responses: Mutex::new(responses.into()),
+        }
+    }
+
+    fn next_response(&self) -> Result<String> {
+        self.responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| anyhow!("Mock LLM has no scripted responses left"))
+    }
+}
+
+#[async_trait]
+impl LlmProvider for MockLlmClient {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    async fn chat(&self, _messages: Vec<Message>) -> Result<String> {
+        self.next_response()
+    }
+
+    async fn chat_stream(&self, _messages: Vec<Message>) -> Result<TokenStream> {
+        let response = self.next_response()?;
+        let chunks: Vec<Result<String>> = response
+            .split_inclusive(' ')
+            .map(|chunk| Ok(chunk.to_string()))
+            .collect();
+        Ok(futures::stream::iter(chunks).boxed())
+    }
+
+    async fn chat_with_tools(&self, _messages: Vec<Message>, _tools: &[ToolDefinition]) -> Result<ChatCompletion> {
+        let response = self.next_response()?;
+
+        #[derive(Deserialize)]
+        struct ScriptedCall {
+            name: String,
+            arguments: serde_json::Value,
+        }
+
+        match serde_json::from_str::<ScriptedCall>(&response) {
+            Ok(call) => Ok(ChatCompletion {
+                content: None,
+                tool_calls: vec![ToolCall {
+                    id: "mock_call".to_string(),
+                    function: FunctionCall {
+                        name: call.name,
+                        arguments: call.arguments.to_string(),
+                    },
+                }],
+            }),
+            Err(_) => Ok(ChatCompletion {
+                content: Some(response),
+                tool_calls: Vec::new(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs chunks through the same line splitting `chat_stream` uses.
+    fn decode(chunks: &[&[u8]]) -> Vec<SseLine> {
+        let mut buffer = Vec::new();
+        let mut lines = Vec::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            buffer.extend_from_slice(chunk);
+            while let Some(line) = next_sse_line(&mut buffer, false) {
+                lines.push(decode_sse_line(&line).unwrap());
+            }
+            if i + 1 == chunks.len() {
+                while let Some(line) = next_sse_line(&mut buffer, true) {
+                    lines.push(decode_sse_line(&line).unwrap());
+                }
+            }
+        }
+        lines.retain(|line| *line != SseLine::Skip);
+        lines
+    }
+
+    fn delta(text: &str) -> SseLine {
+        SseLine::Delta(text.to_string())
+    }
+
+    #[test]
+    fn reassembles_lines_split_across_chunks() {
+        let body = "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n\
+                    data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\n\n\
+                    data: [DONE]\n\n";
+        let bytes = body.as_bytes();
+        let chunks: Vec<&[u8]> = bytes.chunks(7).collect();
+
+        assert_eq!(decode(&chunks), vec![delta("Hel"), delta("lo"), SseLine::Done]);
+    }
+
+    #[test]
+    fn keeps_characters_split_mid_utf8_intact() {
+        let body = "data: {\"choices\":[{\"delta\":{\"content\":\"café ✓\"}}]}\n";
+        let bytes = body.as_bytes();
+        let split = body.find('é').unwrap() + 1;
+
+        assert_eq!(decode(&[&bytes[..split], &bytes[split..]]), vec![delta("café ✓")]);
+    }
+
+    #[test]
+    fn flushes_a_final_line_without_a_newline() {
+        let body = "data: {\"choices\":[{\"delta\":{\"content\":\"first\"}}]}\n\
+                    data: {\"choices\":[{\"delta\":{\"content\":\"last\"}}]}";
+        let bytes = body.as_bytes();
+        let chunks: Vec<&[u8]> = bytes.chunks(11).collect();
+
+        assert_eq!(decode(&chunks), vec![delta("first"), delta("last")]);
+    }
+
+    #[test]
+    fn skips_comments_and_rejects_malformed_chunks() {
+        assert_eq!(decode_sse_line(b": keep-alive\n").unwrap(), SseLine::Skip);
+        assert_eq!(decode_sse_line(b"data: {\"choices\":[{\"delta\":{}}]}\n").unwrap(), SseLine::Skip);
+        assert!(decode_sse_line(b"data: {not json\n").is_err());
+    }
+}
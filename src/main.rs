@@ -1,20 +1,42 @@
 mod agent;
+mod analytics;
+mod chains;
+mod conversation;
+mod erc20;
+mod event_feed;
+mod gas;
+mod indexer;
+mod intent_store;
+mod intent_tools;
 mod llm;
+mod project_resolver;
+mod rpc;
+mod rule_parser;
+mod simulation;
+mod slots;
+mod tx_watcher;
+mod units;
 mod web3;
 mod launchpad_services;
 mod wallet;
 
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
+    response::sse::{Event, KeepAlive, Sse},
     routing::{get, post},
     Router, Json,
     http::{HeaderValue, Method},
 };
+use futures::stream::{BoxStream, StreamExt};
+use std::convert::Infallible;
 use tower_http::cors::Any;
 use crate::agent::TransactionData;
+use crate::simulation::Simulation;
 
 use ethers_core::rand;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::TcpListener;
@@ -24,14 +46,82 @@ use uuid::Uuid;
 use rand::Rng;
 
 // Import your services and agent
-use crate::agent::IntentAgent;
+use crate::agent::{marketing_json, IntentAgent, IntentEvent, UserContext};
+use crate::chains::{ChainConfig, ChainRegistry, UnsupportedChain};
+use crate::conversation::ConversationStore;
+use crate::event_feed::{ChainEvent, EventFeed};
+use crate::indexer::{EventIndexer, EventStore, DEFAULT_INDEX_PATH};
+use crate::intent_store::{IntentQuery, IntentRecord, IntentStore};
+use crate::intent_tools::IntentParseError;
+use crate::llm::LlmConfig;
+use crate::tx_watcher::TxWatcher;
 use crate::web3::Web3Service;
 use crate::wallet::WalletManager;
 
+/// Decimal places of native balances shown in wallet responses.
+const BALANCE_PLACES: u32 = 6;
+
 #[derive(Clone)]
 struct AppState {
-    intent_agent: Arc<RwLock<Option<IntentAgent>>>,
+    chains: Arc<ChainRegistry>,
+    // One per chain with a launchpad, once the agent is initialized
+    runtimes: Arc<RwLock<HashMap<u64, ChainRuntime>>>,
     wallet_manager: Arc<WalletManager>,
+    conversations: ConversationStore,
+    intent_store: IntentStore,
+}
+
+/// The agent and background workers serving one chain's launchpad.
+#[derive(Clone)]
+struct ChainRuntime {
+    chain: ChainConfig,
+    web3_service: Web3Service,
+    agent: IntentAgent,
+    feed: EventFeed,
+    tx_watcher: TxWatcher,
+    indexer: Arc<EventIndexer>,
+}
+
+/// Why a request could not be routed to a chain.
+#[derive(Debug)]
+enum RouteError {
+    NotInitialized,
+    Unsupported(UnsupportedChain),
+    NotDeployed { chain_id: u64, name: String },
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteError::NotInitialized => write!(f, "Intent agent not initialized. Please initialize the agent first."),
+            RouteError::Unsupported(e) => write!(f, "Unsupported network: {}", e),
+            RouteError::NotDeployed { chain_id, name } => {
+                write!(f, "The launchpad is not deployed on {} (chain {})", name, chain_id)
+            }
+        }
+    }
+}
+
+/// The runtime for `chain_id`, or when the request names none, for the
+/// connected wallet's chain, else the registry default.
+async fn route(state: &AppState, chain_id: Option<u64>) -> Result<ChainRuntime, RouteError> {
+    let chain_id = match chain_id {
+        Some(chain_id) => chain_id,
+        None => match state.wallet_manager.get_wallet_info().await {
+            Some(wallet) => wallet.chain_id,
+            None => state.chains.default_chain_id(),
+        },
+    };
+    let chain = state.chains.get(chain_id).map_err(RouteError::Unsupported)?;
+    
+    let runtimes = state.runtimes.read().await;
+    if runtimes.is_empty() {
+        return Err(RouteError::NotInitialized);
+    }
+    runtimes.get(&chain_id).cloned().ok_or_else(|| RouteError::NotDeployed {
+        chain_id,
+        name: chain.name.clone(),
+    })
 }
 
 #[derive(Deserialize)]
@@ -44,6 +134,9 @@ struct SignTransactionRequest {
     transaction_data: TransactionData,
     address: String,
     chain_id: u64,
+    // Set once the wallet has broadcast the transaction
+    intent_id: Option<String>,
+    transaction_hash: Option<String>,
 }
 #[derive(Serialize)]
 struct SignTransactionResponse {
@@ -70,6 +163,15 @@ struct HealthResponse {
 struct CreateIntentRequest {
     user_input: String,
     user_id: Option<i64>,
+    session_id: Option<String>,
+    // Defaults to the connected wallet's chain
+    chain_id: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ChainQuery {
+    // Defaults to the connected wallet's chain
+    chain_id: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -78,6 +180,21 @@ struct SignedIntentRequest {
     signature: String,
     address: String,
     chain_id: u64,
+    session_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct InvestmentsQuery {
+    // Defaults to the connected wallet
+    address: Option<String>,
+    chain_id: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct SubmittedIntentRequest {
+    tx_hash: String,
+    // Only needed when the intent prepared no transaction data
+    chain_id: Option<u64>,
 }
 
 #[derive(Debug,Serialize)]
@@ -88,14 +205,52 @@ struct IntentResponse {
     ai_message: String,
     transaction_hash: Option<String>,
     transaction_data: Option<TransactionData>,  // Add this line!
+    simulation: Option<Simulation>,
     data: Option<serde_json::Value>,
 }
 
 #[derive(Deserialize)]
 struct InitializeAgentRequest {
+    #[serde(default)]
     deepseek_api_key: String,
-    rpc_url: String,
-    contract_address: String,
+    // Overrides `deepseek_api_key` when present
+    llm: Option<LlmConfig>,
+    // Chain the overrides below apply to; the registry default when absent.
+    // Every other chain runs as the registry configures it.
+    chain_id: Option<u64>,
+    rpc_url: Option<String>,
+    contract_address: Option<String>,
+    // Blocks to wait before a submitted transaction counts as confirmed
+    confirmations: Option<u64>,
+    // Multicall3 deployment for batched reads
+    multicall_address: Option<String>,
+    // Block the launchpad was deployed in; event indexing starts there
+    deployment_block: Option<u64>,
+    // SQLite file holding the indexed events of every chain
+    index_path: Option<String>,
+}
+
+impl InitializeAgentRequest {
+    /// `chain` with this request's overrides applied.
+    fn configure(&self, mut chain: ChainConfig) -> ChainConfig {
+        if let Some(rpc_url) = &self.rpc_url {
+            chain.rpc_urls.retain(|url| url != rpc_url);
+            chain.rpc_urls.insert(0, rpc_url.clone());
+        }
+        if let Some(contract_address) = &self.contract_address {
+            chain.launchpad_address = Some(contract_address.clone());
+        }
+        if let Some(confirmations) = self.confirmations {
+            chain.confirmations = confirmations;
+        }
+        if let Some(multicall_address) = &self.multicall_address {
+            chain.multicall_address = multicall_address.clone();
+        }
+        if let Some(deployment_block) = self.deployment_block {
+            chain.deployment_block = deployment_block;
+        }
+        chain
+    }
 }
 
 #[derive(Deserialize)]
@@ -136,9 +291,20 @@ struct WalletBalanceResponse {
 async fn main() {
     println!("🚀 Starting Teemah AI Backend...");
 
-    // Initialize wallet manager with default RPC
-    let default_rpc = "https://data-seed-prebsc-1-s1.binance.org:8545".to_string();
-    let wallet_manager = match WalletManager::new(&default_rpc) {
+    let chains = match ChainRegistry::from_env() {
+        Ok(chains) => Arc::new(chains),
+        Err(e) => {
+            eprintln!("❌ Failed to load chain registry: {:#}", e);
+            std::process::exit(1);
+        }
+    };
+    for chain in chains.chains() {
+        let launchpad = chain.launchpad_address.as_deref().unwrap_or("no launchpad");
+        println!("⛓️ Chain {}: {} ({})", chain.chain_id, chain.name, launchpad);
+    }
+    println!("⛓️ Default chain: {}", chains.default_chain_id());
+
+    let wallet_manager = match WalletManager::new(chains.clone()) {
         Ok(wm) => {
             println!("✅ Wallet manager initialized");
             Arc::new(wm)
@@ -151,8 +317,11 @@ async fn main() {
 
     // Initialize shared state
     let app_state = AppState {
-        intent_agent: Arc::new(RwLock::new(None)),
+        chains,
+        runtimes: Arc::new(RwLock::new(HashMap::new())),
         wallet_manager: wallet_manager.clone(),
+        conversations: ConversationStore::new(),
+        intent_store: IntentStore::new(),
     };
   
    let cors = CorsLayer::new()
@@ -167,8 +336,25 @@ async fn main() {
         .route("/api/health", get(health_handler))
         
         // Intent routes
-        .route("/api/intents", post(create_intent))
+        .route("/api/intents", get(list_intents).post(create_intent))
         .route("/api/intents/signed", post(create_signed_intent))
+        .route("/api/intents/stream", get(stream_intent_query).post(stream_intent_json))
+        .route("/api/intents/:id", get(get_intent))
+        .route("/api/intents/:id/submitted", post(submit_intent))
+        .route("/api/intents/:id/events", get(intent_events))
+        
+        // Project routes
+        .route("/api/projects/:address/analytics", get(get_project_analytics))
+        .route("/api/projects/:address/marketing", get(get_project_marketing))
+        .route("/api/indexer/status", get(get_indexer_status))
+        .route("/api/launchpad/events", get(launchpad_events))
+        
+        // Conversation routes
+        .route("/api/conversations/:id", get(get_conversation).delete(reset_conversation))
+        
+        // Chain routes
+        .route("/api/chains", get(list_chains))
+        .route("/api/gas", get(get_gas_suggestions))
         
         // Agent routes
         .route("/api/agent/initialize", post(initialize_agent))
@@ -179,6 +365,7 @@ async fn main() {
         .route("/api/wallet/disconnect", post(disconnect_wallet))
         .route("/api/wallet/status", get(get_wallet_status))
         .route("/api/wallet/balance", get(get_wallet_balance))
+        .route("/api/wallet/investments", get(get_wallet_investments))
         .route("/api/wallet/sign-message", post(sign_message))
         .route("/api/transactions/sign", post(sign_transaction))
 
@@ -194,8 +381,13 @@ async fn main() {
     println!("🤝 Hello: POST http://{}/api/hello", addr);
     println!("🎯 Intents: POST http://{}/api/intents", addr);
     println!("🔐 Signed Intents: POST http://{}/api/intents/signed", addr);
+    println!("🗂️ Intent Store: GET http://{}/api/intents, GET http://{}/api/intents/:id, POST http://{}/api/intents/:id/submitted", addr, addr, addr);
+    println!("📺 Intent Stream (SSE): GET/POST http://{}/api/intents/stream", addr);
     println!("🤖 Agent Init: POST http://{}/api/agent/initialize", addr);
+    println!("⛓️ Chains: GET http://{}/api/chains", addr);
+    println!("🔔 Launchpad Events (SSE): GET http://{}/api/launchpad/events", addr);
     println!("👛 Wallet Connect: POST http://{}/api/wallet/connect", addr);
+    println!("💬 Conversations: GET/DELETE http://{}/api/conversations/:id", addr);
 
     axum::serve(listener, app).await.unwrap();
 }
@@ -205,8 +397,7 @@ async fn root() -> &'static str {
 }
 
 async fn health_handler(State(state): State<AppState>) -> Json<HealthResponse> {
-    let agent_guard = state.intent_agent.read().await;
-    let agent_initialized = agent_guard.is_some();
+    let agent_initialized = !state.runtimes.read().await.is_empty();
     let wallet_connected = state.wallet_manager.is_connected().await;
     
     Json(HealthResponse {
@@ -232,53 +423,185 @@ async fn initialize_agent(
 ) -> Json<serde_json::Value> {
     println!("🤖 Initializing Intent Agent...");
     
-    println!("📡 RPC URL: {}", payload.rpc_url);
-    println!("📜 Contract Address: {}", payload.contract_address);
-    println!("🧠 DeepSeek API Key (first 10 chars): {}...", 
-             &payload.deepseek_api_key[..10.min(payload.deepseek_api_key.len())]);
-    
-    match Web3Service::new_without_signer(
-        &payload.rpc_url,
-        &payload.contract_address,
-    ) {
-        Ok(web3_service) => {
-            println!("✅ Web3Service initialized successfully (read-only)");
-            
-            let agent = IntentAgent::new("TeemahAgent", &payload.deepseek_api_key, web3_service);
-            println!("✅ IntentAgent created");
-            
-            let mut agent_guard = state.intent_agent.write().await;
-            *agent_guard = Some(agent);
-            
-            Json(serde_json::json!({
-                "success": true,
-                "message": "Intent agent initialized successfully (awaiting wallet connection)",
-                "agent_name": "TeemahAgent",
-                "read_only": true,
-                "wallet_required": true
-            }))
+    let override_chain_id = payload.chain_id.unwrap_or(state.chains.default_chain_id());
+    if let Err(e) = state.chains.get(override_chain_id) {
+        return Json(serde_json::json!({
+            "success": false,
+            "message": format!("Unsupported network: {}", e)
+        }));
+    }
+    if let Some(rpc_url) = &payload.rpc_url {
+        println!("📡 RPC URL for chain {}: {}", override_chain_id, rpc_url);
+    }
+    if let Some(contract_address) = &payload.contract_address {
+        println!("📜 Contract Address for chain {}: {}", override_chain_id, contract_address);
+    }
+    
+    let llm_config = payload.llm.clone().unwrap_or_else(|| LlmConfig::DeepSeek {
+        api_key: payload.deepseek_api_key.clone(),
+        model: None,
+    });
+    if let LlmConfig::DeepSeek { api_key, .. } = &llm_config {
+        println!("🧠 DeepSeek API Key (first 10 chars): {}...", 
+                 &api_key[..10.min(api_key.len())]);
+    }
+    
+    let llm_client = match llm_config.build() {
+        Ok(client) => {
+            println!("🧠 LLM provider: {}", client.name());
+            client
         }
         Err(e) => {
-            eprintln!("❌ Failed to initialize agent: {}", e);
-            Json(serde_json::json!({
+            eprintln!("❌ Failed to initialize LLM provider: {}", e);
+            return Json(serde_json::json!({
                 "success": false,
-                "message": format!("Failed to initialize agent: {}", e)
-            }))
+                "message": format!("Failed to initialize LLM provider: {}", e)
+            }));
         }
+    };
+    let llm_provider = llm_client.name().to_string();
+    
+    let index_path = payload.index_path.as_deref().unwrap_or(DEFAULT_INDEX_PATH);
+    let store = match EventStore::open(index_path) {
+        Ok(store) => store,
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": format!("Failed to open event index {}: {}", index_path, e)
+            }));
+        }
+    };
+    println!("🗂️ Event index: {}", index_path);
+    
+    // Set up every chain before replacing any, so a bad chain leaves the old agent serving
+    let mut services = Vec::new();
+    for chain in state.chains.chains() {
+        let chain = if chain.chain_id == override_chain_id {
+            payload.configure(chain.clone())
+        } else {
+            chain.clone()
+        };
+        if !chain.is_deployed() {
+            continue;
+        }
+        
+        let mut web3_service = match Web3Service::for_chain(&chain) {
+            Ok(web3_service) => web3_service,
+            Err(e) => {
+                eprintln!("❌ Failed to initialize agent on {}: {}", chain.name, e);
+                return Json(serde_json::json!({
+                    "success": false,
+                    "message": format!("Failed to initialize agent on {} (chain {}): {}", chain.name, chain.chain_id, e)
+                }));
+            }
+        };
+        web3_service.set_event_store(store.clone());
+        println!("✅ Web3Service initialized for {} (read-only)", chain.name);
+        println!("📦 Multicall: {:?}", web3_service.multicall_address());
+        
+        services.push((chain, web3_service));
+    }
+    
+    if services.is_empty() {
+        return Json(serde_json::json!({
+            "success": false,
+            "message": "No chain in the registry has a launchpad deployment"
+        }));
     }
+    
+    // The old runtime's background tasks go first, so they never run alongside
+    // the new ones against the same index; receipts it was waiting on carry over
+    let mut runtimes = state.runtimes.write().await;
+    let mut handed_over: HashMap<u64, Vec<(String, ethers::types::H256)>> = HashMap::new();
+    for (chain_id, runtime) in runtimes.drain() {
+        runtime.indexer.stop();
+        runtime.feed.stop();
+        handed_over.insert(chain_id, runtime.tx_watcher.stop());
+    }
+    
+    for (chain, web3_service) in services {
+        let agent = IntentAgent::new(
+            "TeemahAgent",
+            llm_client.clone(),
+            web3_service.clone(),
+            state.conversations.clone(),
+            state.intent_store.clone(),
+        );
+        let feed = EventFeed::start(web3_service.clone(), chain.ws_urls.clone());
+        println!("🔔 Event feed on {}: {:?}", chain.name, feed.transport());
+        let tx_watcher = TxWatcher::new(
+            web3_service.clone(),
+            state.intent_store.clone(),
+            chain.confirmations,
+        ).with_feed(&feed);
+        println!("👀 Receipt watcher ready on {} ({} confirmation(s))", chain.name, tx_watcher.confirmations());
+        for (intent_id, tx_hash) in handed_over.remove(&chain.chain_id).unwrap_or_default() {
+            tx_watcher.watch(intent_id, tx_hash);
+        }
+        let indexer = EventIndexer::new(
+            web3_service.clone(),
+            store.clone(),
+            chain.deployment_block,
+            tx_watcher.confirmations(),
+        ).with_feed(&feed).start();
+        
+        runtimes.insert(chain.chain_id, ChainRuntime {
+            chain,
+            web3_service,
+            agent,
+            feed,
+            tx_watcher,
+            indexer: Arc::new(indexer),
+        });
+    }
+    
+    for (chain_id, watches) in handed_over {
+        for (intent_id, _) in watches {
+            println!("⚠️ Chain {} is no longer served; intent {} stays submitted", chain_id, intent_id);
+        }
+    }
+    println!("✅ IntentAgent created for {} chain(s)", runtimes.len());
+    
+    let mut chains: Vec<serde_json::Value> = runtimes
+        .values()
+        .map(|runtime| serde_json::json!({
+            "chain_id": runtime.chain.chain_id,
+            "name": runtime.chain.name,
+            "contract_address": runtime.chain.launchpad_address,
+            "confirmations": runtime.tx_watcher.confirmations(),
+        }))
+        .collect();
+    chains.sort_by_key(|chain| chain["chain_id"].as_u64());
+    drop(runtimes);
+    
+    Json(serde_json::json!({
+        "success": true,
+        "message": "Intent agent initialized successfully (awaiting wallet connection)",
+        "agent_name": "TeemahAgent",
+        "llm_provider": llm_provider,
+        "read_only": true,
+        "wallet_required": true,
+        "default_chain_id": state.chains.default_chain_id(),
+        "chains": chains,
+        "index_path": index_path
+    }))
 }
 
 async fn get_agent_status(State(state): State<AppState>) -> Json<serde_json::Value> {
-    let agent_guard = state.intent_agent.read().await;
+    let runtimes = state.runtimes.read().await;
     let wallet_connected = state.wallet_manager.is_connected().await;
     
-    if let Some(agent) = agent_guard.as_ref() {
-        let intents = agent.get_active_intents().await;
+    if let Some(runtime) = runtimes.values().next() {
+        let active_intents = state.intent_store.active_count().await;
+        let mut chain_ids: Vec<u64> = runtimes.keys().copied().collect();
+        chain_ids.sort();
         
         Json(serde_json::json!({
             "initialized": true,
-            "agent_name": agent.name,
-            "active_intents": intents.len(),
+            "agent_name": runtime.agent.name,
+            "llm_provider": runtime.agent.llm_provider_name(),
+            "chain_ids": chain_ids,
+            "active_intents": active_intents,
             "wallet_connected": wallet_connected,
             "requires_wallet": true,
             "status": "ready"
@@ -294,6 +617,37 @@ async fn get_agent_status(State(state): State<AppState>) -> Json<serde_json::Val
     }
 }
 
+async fn list_chains(State(state): State<AppState>) -> Json<serde_json::Value> {
+    let runtimes = state.runtimes.read().await;
+    let chains: Vec<serde_json::Value> = state.chains
+        .chains()
+        .map(|chain| {
+            // Serve the configuration actually in use, overrides included
+            let chain = runtimes.get(&chain.chain_id).map_or(chain, |runtime| &runtime.chain);
+            serde_json::json!({
+                "chain_id": chain.chain_id,
+                "name": chain.name,
+                "native_symbol": chain.native_symbol,
+                "launchpad_address": chain.launchpad_address,
+                "multicall_address": chain.multicall_address,
+                "explorer_url": chain.explorer_url,
+                "confirmations": chain.confirmations,
+                "rpc_quorum": chain.rpc_quorum,
+                "fee_model": chain.fee_model,
+                "active": runtimes.contains_key(&chain.chain_id),
+                "rpc_endpoints": runtimes.get(&chain.chain_id).map(|runtime| runtime.web3_service.rpc_health()),
+                "event_transport": runtimes.get(&chain.chain_id).map(|runtime| runtime.feed.transport()),
+            })
+        })
+        .collect();
+    
+    Json(serde_json::json!({
+        "success": true,
+        "default_chain_id": state.chains.default_chain_id(),
+        "chains": chains,
+    }))
+}
+
 async fn connect_wallet(
     State(state): State<AppState>,
     Json(payload): Json<WalletConnectRequest>,
@@ -389,21 +743,48 @@ async fn sign_transaction(
         });
     }
     
-    println!("⚠️ IMPORTANT: In production, transactions should be signed client-side with ethers.js");
     println!("📋 Transaction details:");
     println!("   To: {}", payload.transaction_data.to);
     println!("   Description: {}", payload.transaction_data.description);
     println!("   Chain ID: {}", payload.transaction_data.chain_id);
     println!("   Value: {}", payload.transaction_data.value);
     
-    // Simulate a transaction hash
-    let mock_hash = format!("0x{:x}", rand::random::<u64>());
+    if payload.transaction_data.chain_id != payload.chain_id {
+        return Json(SignTransactionResponse {
+            success: false,
+            transaction_hash: None,
+            message: format!(
+                "Transaction is for chain {} but the wallet is on chain {}. Switch networks and try again.",
+                payload.transaction_data.chain_id, payload.chain_id
+            ),
+        });
+    }
     
-    Json(SignTransactionResponse {
-        success: true,
-        transaction_hash: Some(mock_hash),
-        message: "Transaction signing simulated. In production, this would be signed by the user's wallet.".to_string(),
-    })
+    // The backend holds no user keys: the wallet signs and broadcasts, then reports the hash here
+    let (Some(intent_id), Some(tx_hash)) = (&payload.intent_id, &payload.transaction_hash) else {
+        return Json(SignTransactionResponse {
+            success: false,
+            transaction_hash: None,
+            message: "Sign and broadcast this transaction in your wallet, then send its intent_id and transaction_hash.".to_string(),
+        });
+    };
+    
+    match record_submission(&state, intent_id, tx_hash, Some(payload.chain_id)).await {
+        Ok((intent, watching)) => Json(SignTransactionResponse {
+            success: true,
+            transaction_hash: intent.tx_hash,
+            message: if watching {
+                "Transaction submitted. Waiting for on-chain confirmation.".to_string()
+            } else {
+                "Transaction submitted, but no receipt watcher is running.".to_string()
+            },
+        }),
+        Err(message) => Json(SignTransactionResponse {
+            success: false,
+            transaction_hash: None,
+            message,
+        }),
+    }
 }
 async fn get_wallet_status(
     State(state): State<AppState>,
@@ -414,10 +795,7 @@ async fn get_wallet_status(
         if let Some(wallet_info) = state.wallet_manager.get_wallet_info().await {
             let balance_result = state.wallet_manager.get_balance().await;
             let (balance, balance_eth) = match balance_result {
-                Ok(bal) => {
-                    let eth_balance = bal.as_u128() as f64 / 1e18;
-                    (Some(bal.to_string()), Some(format!("{:.6}", eth_balance)))
-                }
+                Ok(bal) => (Some(bal.to_string()), Some(units::format_units_truncated(bal, units::ETHER_DECIMALS, BALANCE_PLACES))),
                 Err(_) => (None, None),
             };
             
@@ -457,11 +835,10 @@ async fn get_wallet_balance(
 ) -> Json<WalletBalanceResponse> {
     match state.wallet_manager.get_balance().await {
         Ok(balance) => {
-            let eth_balance = balance.as_u128() as f64 / 1e18;
             Json(WalletBalanceResponse {
                 success: true,
                 balance: Some(balance.to_string()),
-                balance_eth: Some(format!("{:.6}", eth_balance)),
+                balance_eth: Some(units::format_units_truncated(balance, units::ETHER_DECIMALS, BALANCE_PLACES)),
                 message: None,
             })
         }
@@ -476,6 +853,63 @@ async fn get_wallet_balance(
     }
 }
 
+async fn get_wallet_investments(
+    State(state): State<AppState>,
+    Query(query): Query<InvestmentsQuery>,
+) -> Json<serde_json::Value> {
+    let address = match query.address {
+        Some(address) => address,
+        None => match state.wallet_manager.get_wallet_info().await {
+            Some(wallet) => wallet.address,
+            None => {
+                return Json(serde_json::json!({
+                    "success": false,
+                    "message": "No wallet connected. Connect a wallet or pass ?address=",
+                }));
+            }
+        },
+    };
+    let address = match crate::slots::validate_address(&address) {
+        Ok(address) => address,
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": e,
+            }));
+        }
+    };
+    
+    let runtime = match route(&state, query.chain_id).await {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": e.to_string(),
+            }));
+        }
+    };
+    
+    let investments = match runtime.agent.investments(&address).await {
+        Ok(investments) => investments,
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": format!("Failed to get investments: {}", e),
+            }));
+        }
+    };
+    // History is complete up to the index's checkpoint plus the blocks scanned past it
+    let indexed_to_block = runtime.indexer.stats().await.ok().and_then(|stats| stats.last_block);
+    
+    Json(serde_json::json!({
+        "success": true,
+        "address": address,
+        "chain_id": runtime.chain.chain_id,
+        "investments": investments,
+        "indexed_to_block": indexed_to_block,
+    }))
+}
+
 async fn sign_message(
     State(state): State<AppState>,
     Json(payload): Json<serde_json::Value>,
@@ -508,24 +942,376 @@ async fn sign_message(
     }
 }
 
+// Parse errors get their own status so clients can ask the user to rephrase
+fn failure_status(error: &anyhow::Error) -> String {
+    if error.downcast_ref::<IntentParseError>().is_some() {
+        "invalid_intent".to_string()
+    } else {
+        "failed".to_string()
+    }
+}
+
+async fn get_conversation(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Json<serde_json::Value> {
+    match state.conversations.get(&id).await {
+        Some(conversation) => Json(serde_json::json!({
+            "success": true,
+            "conversation": conversation,
+        })),
+        None => Json(serde_json::json!({
+            "success": false,
+            "message": format!("Conversation not found: {}", id),
+        })),
+    }
+}
+
+async fn reset_conversation(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Json<serde_json::Value> {
+    let existed = state.conversations.reset(&id).await;
+    println!("🧹 Conversation reset: {} (existed: {})", id, existed);
+    
+    Json(serde_json::json!({
+        "success": true,
+        "message": if existed { "Conversation history cleared" } else { "No history for this conversation" },
+        "conversation_id": id,
+    }))
+}
+
+async fn list_intents(
+    State(state): State<AppState>,
+    Query(query): Query<IntentQuery>,
+) -> Json<serde_json::Value> {
+    let intents = state.intent_store.list(&query).await;
+    
+    Json(serde_json::json!({
+        "success": true,
+        "count": intents.len(),
+        "intents": intents,
+    }))
+}
+
+async fn get_intent(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Json<serde_json::Value> {
+    match state.intent_store.get(&id).await {
+        Some(intent) => Json(serde_json::json!({
+            "success": true,
+            "intent": intent,
+        })),
+        None => Json(serde_json::json!({
+            "success": false,
+            "message": format!("Intent not found: {}", id),
+        })),
+    }
+}
+
+// The client reports the hash once its wallet has broadcast the prepared transaction
+async fn submit_intent(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(payload): Json<SubmittedIntentRequest>,
+) -> Json<serde_json::Value> {
+    match record_submission(&state, &id, &payload.tx_hash, payload.chain_id).await {
+        Ok((intent, watching)) => {
+            let explorer_url = match route(&state, intent_chain_id(&intent).or(payload.chain_id)).await {
+                Ok(runtime) => intent.tx_hash.as_deref().map(|hash| runtime.chain.explorer_tx_url(hash)),
+                Err(_) => None,
+            };
+            Json(serde_json::json!({
+                "success": true,
+                "intent": intent,
+                "watching": watching,
+                "explorer_url": explorer_url,
+            }))
+        }
+        Err(message) => Json(serde_json::json!({
+            "success": false,
+            "message": message,
+        })),
+    }
+}
+
+/// Chain the intent's prepared transaction was built for.
+fn intent_chain_id(record: &IntentRecord) -> Option<u64> {
+    record.result.as_ref()?.transaction_data.as_ref().map(|tx| tx.chain_id)
+}
+
+/// Marks the intent submitted and hands the hash to the receipt watcher of
+/// the chain its transaction was prepared for, falling back to `chain_id`.
+/// Returns the record and whether a watcher is following it.
+async fn record_submission(state: &AppState, intent_id: &str, tx_hash: &str, chain_id: Option<u64>) -> Result<(IntentRecord, bool), String> {
+    let tx_hash = tx_hash.trim();
+    let parsed_hash = tx_hash
+        .strip_prefix("0x")
+        .filter(|hex| hex.len() == 64)
+        .and_then(|_| tx_hash.parse::<ethers::types::H256>().ok())
+        .ok_or_else(|| format!("Invalid transaction hash: {}", tx_hash))?;
+    
+    let intent = state
+        .intent_store
+        .mark_submitted(intent_id, &tx_hash.to_lowercase())
+        .await
+        .map_err(|e| e.to_string())?;
+    println!("📨 Intent {} submitted: {}", intent_id, tx_hash);
+    
+    match route(state, intent_chain_id(&intent).or(chain_id)).await {
+        Ok(runtime) => {
+            runtime.tx_watcher.watch(intent_id.to_string(), parsed_hash);
+            Ok((intent, true))
+        }
+        Err(e) => {
+            println!("⚠️ No receipt watcher ({}); intent {} stays submitted", e, intent_id);
+            Ok((intent, false))
+        }
+    }
+}
+
+// Streams an intent's record every time it changes, until it settles
+async fn intent_events(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> IntentEventStream {
+    let (tx, rx) = futures::channel::mpsc::unbounded::<Event>();
+    
+    // Subscribe before reading the current record so no change slips in between
+    let mut updates = state.intent_store.subscribe();
+    match state.intent_store.get(&id).await {
+        Some(current) => {
+            let settled = current.state.is_terminal();
+            let _ = tx.unbounded_send(record_event(&current));
+            if !settled {
+                tokio::spawn(async move {
+                    loop {
+                        match updates.recv().await {
+                            Ok(record) if record.intent_id == id => {
+                                if tx.unbounded_send(record_event(&record)).is_err() || record.state.is_terminal() {
+                                    break;
+                                }
+                            }
+                            Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+        }
+        None => {
+            let _ = tx.unbounded_send(Event::default().event("failed").data(format!("Intent not found: {}", id)));
+        }
+    }
+    
+    Sse::new(rx.map(Ok).boxed()).keep_alive(KeepAlive::default())
+}
+
+// Streams launchpad events as they are mined, ahead of confirmation
+async fn launchpad_events(
+    State(state): State<AppState>,
+    Query(query): Query<ChainQuery>,
+) -> IntentEventStream {
+    let (tx, rx) = futures::channel::mpsc::unbounded::<Event>();
+    
+    match route(&state, query.chain_id).await {
+        Ok(runtime) => {
+            let mut events = runtime.feed.subscribe();
+            tokio::spawn(async move {
+                loop {
+                    match events.recv().await {
+                        Ok(ChainEvent::Launchpad(event)) => {
+                            let sse_event = Event::default()
+                                .event("launchpad")
+                                .json_data(&event)
+                                .unwrap_or_else(|e| Event::default().event("failed").data(e.to_string()));
+                            if tx.unbounded_send(sse_event).is_err() {
+                                break;
+                            }
+                        }
+                        Ok(ChainEvent::NewHead { .. }) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+        Err(e) => {
+            let _ = tx.unbounded_send(Event::default().event("failed").data(e.to_string()));
+        }
+    }
+    
+    Sse::new(rx.map(Ok).boxed()).keep_alive(KeepAlive::default())
+}
+
+fn record_event(record: &IntentRecord) -> Event {
+    Event::default()
+        .event("intent")
+        .json_data(record)
+        .unwrap_or_else(|e| Event::default().event("failed").data(e.to_string()))
+}
+
+async fn get_project_analytics(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<ChainQuery>,
+) -> Json<serde_json::Value> {
+    let address = match crate::slots::validate_address(&address) {
+        Ok(address) => address,
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": e,
+            }));
+        }
+    };
+    
+    let runtime = match route(&state, query.chain_id).await {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": e.to_string(),
+            }));
+        }
+    };
+    
+    match runtime.agent.project_analytics(&address).await {
+        Ok(analytics) => Json(serde_json::json!({
+            "success": true,
+            "analytics": analytics,
+        })),
+        Err(e) => Json(serde_json::json!({
+            "success": false,
+            "message": format!("Failed to get project analytics: {}", e),
+        })),
+    }
+}
+
+async fn get_project_marketing(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<ChainQuery>,
+) -> Json<serde_json::Value> {
+    let address = match crate::slots::validate_address(&address) {
+        Ok(address) => address,
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": e,
+            }));
+        }
+    };
+    
+    let runtime = match route(&state, query.chain_id).await {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": e.to_string(),
+            }));
+        }
+    };
+    
+    match runtime.web3_service.get_project_marketing_info(&address).await {
+        Ok(Some(marketing)) => Json(serde_json::json!({
+            "success": true,
+            "chain_id": runtime.chain.chain_id,
+            "address": address,
+            "marketing": marketing_json(&marketing),
+        })),
+        Ok(None) => Json(serde_json::json!({
+            "success": false,
+            "message": format!("No marketing allocation for project {}", address),
+        })),
+        Err(e) => Json(serde_json::json!({
+            "success": false,
+            "message": format!("Failed to get marketing info: {}", e),
+        })),
+    }
+}
+
+async fn get_gas_suggestions(
+    State(state): State<AppState>,
+    Query(query): Query<ChainQuery>,
+) -> Json<serde_json::Value> {
+    let runtime = match route(&state, query.chain_id).await {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": e.to_string(),
+            }));
+        }
+    };
+    
+    match runtime.web3_service.suggest_fees().await {
+        Ok(fees) => Json(serde_json::json!({
+            "success": true,
+            "chain_id": runtime.chain.chain_id,
+            "fees": fees,
+        })),
+        Err(e) => Json(serde_json::json!({
+            "success": false,
+            "message": format!("Failed to suggest gas fees: {}", e),
+        })),
+    }
+}
+
+async fn get_indexer_status(
+    State(state): State<AppState>,
+    Query(query): Query<ChainQuery>,
+) -> Json<serde_json::Value> {
+    let runtime = match route(&state, query.chain_id).await {
+        Ok(runtime) => runtime,
+        Err(RouteError::NotInitialized) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": "Event indexer not running. Please initialize the agent first.",
+            }));
+        }
+        Err(e) => {
+            return Json(serde_json::json!({
+                "success": false,
+                "message": e.to_string(),
+            }));
+        }
+    };
+    
+    match runtime.indexer.stats().await {
+        Ok(stats) => Json(serde_json::json!({
+            "success": true,
+            "chain_id": runtime.chain.chain_id,
+            "indexer": stats,
+        })),
+        Err(e) => Json(serde_json::json!({
+            "success": false,
+            "message": format!("Failed to read event index: {}", e),
+        })),
+    }
+}
+
+fn random_user_id() -> i64 {
+    let mut rng = rand::thread_rng();
+    rng.gen_range(100000..999999) as i64
+}
+
 async fn create_intent(
     State(state): State<AppState>,
     Json(payload): Json<CreateIntentRequest>,
 ) -> Json<IntentResponse> {
     println!("🎯 Intent received (unsigned): {}", payload.user_input);
     
-    let agent_guard = state.intent_agent.read().await;
-    
-    match agent_guard.as_ref() {
-        Some(agent) => {
-            let user_id = payload.user_id.unwrap_or_else(|| {
-                let mut rng = rand::thread_rng();
-                rng.gen_range(100000..999999) as i64
-            });
+    match route(&state, payload.chain_id).await {
+        Ok(runtime) => {
+            let agent = &runtime.agent;
+            let user_id = payload.user_id.unwrap_or_else(random_user_id);
             
             println!("🔑 Using user_id: {} (generated automatically)", user_id);
+            let wallet_address = state.wallet_manager.get_wallet_info().await.map(|w| w.address);
+            let ctx = UserContext::new(user_id, payload.session_id.clone()).with_wallet(wallet_address);
             
-            match agent.process_user_intent(user_id, &payload.user_input).await {
+            match agent.process_user_intent(&ctx, &payload.user_input).await {
                 Ok(result) => {
                       println!("📦 Intent result: success={}, has_tx_data={}", 
                              result.success, result.transaction_data.is_some());
@@ -534,12 +1320,13 @@ async fn create_intent(
                     println!("📝 Transaction data chain_id: {}", tx_data.chain_id);
                     }
                     Json(IntentResponse {
+                        status: result.status().to_string(),
                         intent_id: result.intent_id,
-                        status: if result.success { "completed".to_string() } else { "failed".to_string() },
                         message: result.message,
                         ai_message: result.ai_message,
                         transaction_hash: result.transaction_hash,
                         transaction_data: result.transaction_data,  // Add this line!
+                        simulation: result.simulation,
                         data: result.data,
                     })
                 }
@@ -549,17 +1336,18 @@ async fn create_intent(
                     
                     Json(IntentResponse {
                         intent_id: Uuid::new_v4().to_string(),
-                        status: "failed".to_string(),
+                        status: failure_status(&e),
                         message: error_message,
                         ai_message: fallback_ai_response,
                         transaction_hash: None,
                         transaction_data: None,  // Add this line!
+                        simulation: None,
                         data: None,
                     })
                 }
             }
         }
-        None => {
+        Err(RouteError::NotInitialized) => {
             Json(IntentResponse {
                 intent_id: Uuid::new_v4().to_string(),
                 status: "failed".to_string(),
@@ -567,9 +1355,11 @@ async fn create_intent(
                 ai_message: "🤖 Please initialize the AI agent first by clicking 'Initialize Agent' in the settings.".to_string(),
                 transaction_hash: None,
                 transaction_data: None,  // Add this line!
+                simulation: None,
                 data: None,
             })
         }
+        Err(e) => Json(unroutable_response(&e)),
     }
 }
 
@@ -589,6 +1379,7 @@ async fn create_signed_intent(
             ai_message: "🔐 Please connect your wallet first to execute transactions!".to_string(),
             transaction_hash: None,
             transaction_data: None,
+            simulation: None,
             data: None,
         });
     }
@@ -596,21 +1387,25 @@ async fn create_signed_intent(
     println!("📝 Signature provided: {}", payload.signature);
     println!("⚠️ Note: Signature verification is simulated in this example");
     
-    let agent_guard = state.intent_agent.read().await;
-    
-    match agent_guard.as_ref() {
-        Some(agent) => {
+    match route(&state, Some(payload.chain_id)).await {
+        Ok(runtime) => {
+            let agent = &runtime.agent;
             // Generate user_id from address hash
             let user_id = (payload.address.clone() + &payload.chain_id.to_string())
                 .bytes()
                 .fold(0i64, |acc, b| acc.wrapping_add(b as i64));
+            // Wallet sessions share one conversation per address unless the client picks its own
+            let session_id = payload.session_id.clone()
+                .unwrap_or_else(|| format!("wallet-{}", payload.address.to_lowercase()));
+            let ctx = UserContext::new(user_id, Some(session_id)).with_wallet(Some(payload.address.clone()));
             
-            match agent.process_user_intent(user_id, &payload.user_input).await {
+            match agent.process_user_intent(&ctx, &payload.user_input).await {
                 Ok(result) => {
                     // DEBUG LOGGING
                     println!("🔄 Intent result received:");
                     println!("   Success: {}", result.success);
                     println!("   Has transaction_data: {}", result.transaction_data.is_some());
+                    let status = result.status().to_string();
                     
                     // Start with the result data or empty object
                     let mut data = match result.data {
@@ -656,12 +1451,13 @@ async fn create_signed_intent(
                     
                     // Create the response
                     let response = IntentResponse {
+                        status,
                         intent_id: result.intent_id,
-                        status: if result.success { "completed".to_string() } else { "failed".to_string() },
                         message: result.message,
                         ai_message: result.ai_message,
                         transaction_hash: result.transaction_hash,
                         transaction_data: result.transaction_data,
+                        simulation: result.simulation,
                         data: Some(data),
                     };
                     
@@ -688,17 +1484,18 @@ async fn create_signed_intent(
                     
                     Json(IntentResponse {
                         intent_id: Uuid::new_v4().to_string(),
-                        status: "failed".to_string(),
+                        status: failure_status(&e),
                         message: error_message,
                         ai_message: fallback_ai_response,
                         transaction_hash: None,
                         transaction_data: None,
+                        simulation: None,
                         data: None,
                     })
                 }
             }
         }
-        None => {
+        Err(RouteError::NotInitialized) => {
             Json(IntentResponse {
                 intent_id: Uuid::new_v4().to_string(),
                 status: "failed".to_string(),
@@ -706,8 +1503,76 @@ async fn create_signed_intent(
                 ai_message: "🤖 Please initialize the AI agent first.".to_string(),
                 transaction_hash: None,
                 transaction_data:None,
+                simulation: None,
                 data: None,
             })
         }
+        Err(e) => Json(unroutable_response(&e)),
     }
 }
+
+// The request named, or its wallet is on, a chain the agent does not serve
+fn unroutable_response(error: &RouteError) -> IntentResponse {
+    IntentResponse {
+        intent_id: Uuid::new_v4().to_string(),
+        status: "unsupported_chain".to_string(),
+        message: error.to_string(),
+        ai_message: format!("⛓️ {}. Please switch your wallet to a supported network.", error),
+        transaction_hash: None,
+        transaction_data: None,
+        simulation: None,
+        data: None,
+    }
+}
+
+type IntentEventStream = Sse<BoxStream<'static, Result<Event, Infallible>>>;
+
+async fn stream_intent_query(
+    State(state): State<AppState>,
+    Query(payload): Query<CreateIntentRequest>,
+) -> IntentEventStream {
+    stream_intent(state, payload).await
+}
+
+async fn stream_intent_json(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateIntentRequest>,
+) -> IntentEventStream {
+    stream_intent(state, payload).await
+}
+
+async fn stream_intent(state: AppState, payload: CreateIntentRequest) -> IntentEventStream {
+    println!("📺 Intent received (streaming): {}", payload.user_input);
+    
+    let (tx, rx) = futures::channel::mpsc::unbounded::<IntentEvent>();
+    
+    // Clone the agent out of the lock so a long generation doesn't block re-initialization
+    match route(&state, payload.chain_id).await {
+        Ok(ChainRuntime { agent, .. }) => {
+            let user_id = payload.user_id.unwrap_or_else(random_user_id);
+            let wallet_address = state.wallet_manager.get_wallet_info().await.map(|w| w.address);
+            let ctx = UserContext::new(user_id, payload.session_id.clone()).with_wallet(wallet_address);
+            tokio::spawn(async move {
+                if let Err(e) = agent.process_user_intent_streaming(&ctx, &payload.user_input, &tx).await {
+                    eprintln!("❌ Streaming intent failed: {}", e);
+                    let _ = tx.unbounded_send(IntentEvent::Failed {
+                        message: format!("Intent processing failed: {}", e),
+                    });
+                }
+            });
+        }
+        Err(e) => {
+            let _ = tx.unbounded_send(IntentEvent::Failed { message: e.to_string() });
+        }
+    }
+    
+    let events = rx.map(|event| {
+        let sse_event = Event::default()
+            .event(event.stage())
+            .json_data(&event)
+            .unwrap_or_else(|e| Event::default().event("failed").data(e.to_string()));
+        Ok(sse_event)
+    });
+    
+    Sse::new(events.boxed()).keep_alive(KeepAlive::default())
+}
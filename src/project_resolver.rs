@@ -0,0 +1,207 @@
+use crate::launchpad_services::Project;
+use crate::slots::validate_address;
+use crate::web3::Web3Service;
+use anyhow::Result;
+use ethers::types::Address;
+use ethers::utils::to_checksum;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// How long a `getProject` result is trusted before it is fetched again.
+const CACHE_TTL: Duration = Duration::from_secs(300);
+/// Search hits whose details are fetched and ranked.
+const MAX_CANDIDATES: usize = 20;
+/// Matches offered back to the user when a reference is ambiguous.
+const MAX_CHOICES: usize = 5;
+/// Lowest score still treated as a match at all.
+const MIN_SCORE: u32 = 55;
+/// How far the best match must lead the runner-up to be picked outright.
+const CLEAR_LEAD: u32 = 15;
+
+const EXACT_SYMBOL: u32 = 100;
+const EXACT_NAME: u32 = 95;
+const NAME_PREFIX: u32 = 80;
+const NAME_CONTAINS: u32 = 65;
+
+#[derive(Debug, Clone)]
+pub struct ProjectMatch {
+    pub project: Project,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone)]
+pub enum Resolution {
+    /// Checksummed address, with details when it was found by name or symbol.
+    Found { address: String, project: Option<Box<Project>> },
+    /// Several projects fit the reference equally well, best first.
+    Ambiguous(Vec<ProjectMatch>),
+    NotFound,
+}
+
+#[derive(Debug, Clone)]
+struct CachedProject {
+    project: Project,
+    fetched_at: Instant,
+}
+
+/// Maps what users call a project ("PEPE", "moon cat") to its address, using
+/// the contract's `searchProjects` plus a local cache of `getProject` results.
+#[derive(Debug, Clone)]
+pub struct ProjectResolver {
+    web3_service: Web3Service,
+    cache: Arc<RwLock<HashMap<Address, CachedProject>>>,
+}
+
+impl ProjectResolver {
+    pub fn new(web3_service: Web3Service) -> Self {
+        Self {
+            web3_service,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Resolves an address, name or symbol. Addresses are only validated, not
+    /// looked up, so a freshly created project is never "not found".
+    pub async fn resolve(&self, reference: &str) -> Result<Resolution> {
+        let reference = reference.trim().trim_start_matches('$');
+        if reference.starts_with("0x") || reference.starts_with("0X") {
+            let address = validate_address(reference).map_err(|e| anyhow::anyhow!("{}", e))?;
+            return Ok(Resolution::Found { address, project: None });
+        }
+
+        let mut candidates: Vec<Project> = Vec::new();
+        match self.web3_service.search_project_addresses(reference).await {
+            Ok(addresses) => {
+                // Hits not cached are loaded together, in one multicall
+                let mut stale = Vec::new();
+                for address in addresses.into_iter().take(MAX_CANDIDATES) {
+                    match self.cached(address).await {
+                        Some(project) => candidates.push(project),
+                        None => stale.push(address),
+                    }
+                }
+                if !stale.is_empty() {
+                    let batch = self.web3_service.get_projects(&stale).await;
+                    for failure in &batch.failures {
+                        println!("⚠️ Could not load search hit {:?}: {}", failure.address, failure.reason);
+                    }
+                    self.remember(&batch.projects).await;
+                    candidates.extend(batch.projects);
+                }
+            }
+            Err(e) => println!("⚠️ searchProjects failed ({}), using cached projects only", e),
+        }
+        for cached in self.cache.read().await.values() {
+            if !candidates.iter().any(|p| p.address == cached.project.address) {
+                candidates.push(cached.project.clone());
+            }
+        }
+
+        let ranked = rank(reference, candidates);
+        println!("🔎 Resolved '{}' to {} candidate(s)", reference, ranked.len());
+        Ok(pick(ranked))
+    }
+
+    /// A project's details, when cached and still fresh.
+    async fn cached(&self, address: Address) -> Option<Project> {
+        let cache = self.cache.read().await;
+        let cached = cache.get(&address)?;
+        (cached.fetched_at.elapsed() < CACHE_TTL).then(|| cached.project.clone())
+    }
+
+    /// Adds projects fetched elsewhere (e.g. listings) to the cache.
+    pub async fn remember(&self, projects: &[Project]) {
+        let mut cache = self.cache.write().await;
+        let now = Instant::now();
+        for project in projects {
+            cache.insert(project.address, CachedProject { project: project.clone(), fetched_at: now });
+        }
+    }
+}
+
+fn rank(reference: &str, candidates: Vec<Project>) -> Vec<ProjectMatch> {
+    let mut ranked: Vec<ProjectMatch> = candidates
+        .into_iter()
+        .map(|project| ProjectMatch { score: score(reference, &project), project })
+        .filter(|m| m.score >= MIN_SCORE)
+        .collect();
+    ranked.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.project.name.cmp(&b.project.name)));
+    ranked
+}
+
+/// Picks the winner, unless several projects share the symbol or no match
+/// clearly leads.
+fn pick(ranked: Vec<ProjectMatch>) -> Resolution {
+    match ranked.as_slice() {
+        [] => Resolution::NotFound,
+        [only] => found(&only.project),
+        [best, second, ..] => {
+            let shared_symbol = best.score == EXACT_SYMBOL && second.score == EXACT_SYMBOL;
+            if !shared_symbol && best.score >= second.score + CLEAR_LEAD {
+                found(&best.project)
+            } else {
+                Resolution::Ambiguous(ranked.into_iter().take(MAX_CHOICES).collect())
+            }
+        }
+    }
+}
+
+fn found(project: &Project) -> Resolution {
+    Resolution::Found {
+        address: to_checksum(&project.address, None),
+        project: Some(Box::new(project.clone())),
+    }
+}
+
+fn score(reference: &str, project: &Project) -> u32 {
+    let wanted = normalize(reference);
+    let symbol = normalize(&project.symbol);
+    let name = normalize(&project.name);
+    if wanted.is_empty() {
+        return 0;
+    }
+
+    if symbol == wanted {
+        EXACT_SYMBOL
+    } else if name == wanted {
+        EXACT_NAME
+    } else if name.starts_with(&wanted) {
+        NAME_PREFIX
+    } else if name.contains(&wanted) {
+        NAME_CONTAINS
+    } else {
+        // Typos: "pepee", "moncat"
+        let best = similarity(&wanted, &symbol).max(similarity(&wanted, &name));
+        (best * 80.0) as u32
+    }
+}
+
+/// Lower-case letters and digits only, so "Moon-Cat" matches "moon cat".
+fn normalize(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// 1.0 for identical strings, falling towards 0.0 with edit distance.
+fn similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 0.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / longest as f64
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
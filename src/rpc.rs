@@ -0,0 +1,555 @@
+use async_trait::async_trait;
+use ethers::providers::{Http, HttpClientError, JsonRpcClient, JsonRpcError, Provider, ProviderError, RpcError};
+use ethers::types::U64;
+use futures::future::join_all;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Passes over every endpoint before a request gives up.
+const MAX_ROUNDS: u32 = 3;
+/// Pause before the second round over the endpoints; doubles for each round after.
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+/// An endpoint that failed is skipped for this long, doubling per consecutive
+/// failure up to `MAX_COOLDOWN`.
+const BASE_COOLDOWN: Duration = Duration::from_secs(2);
+const MAX_COOLDOWN: Duration = Duration::from_secs(60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Weight of the newest sample in an endpoint's average latency.
+const LATENCY_SMOOTHING: f64 = 0.3;
+
+/// Ethereum provider over every RPC endpoint of a chain.
+pub type RpcProvider = Provider<FailoverClient>;
+
+/// What one endpoint has been like to talk to.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EndpointHealth {
+    /// Host only, since RPC URLs often carry API keys in the path.
+    pub host: String,
+    pub healthy: bool,
+    pub latency_ms: Option<f64>,
+    pub requests: u64,
+    pub failures: u64,
+    pub consecutive_failures: u32,
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    latency_ms: Option<f64>,
+    requests: u64,
+    failures: u64,
+    consecutive_failures: u32,
+    cooldown_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    host: String,
+    http: Http,
+    state: Mutex<EndpointState>,
+}
+
+impl Endpoint {
+    fn is_cooling_down(&self, now: Instant) -> bool {
+        self.state().cooldown_until.is_some_and(|until| until > now)
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, EndpointState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn record_success(&self, elapsed: Duration) {
+        let mut state = self.state();
+        let sample = elapsed.as_secs_f64() * 1000.0;
+        state.latency_ms = Some(match state.latency_ms {
+            Some(average) => average + LATENCY_SMOOTHING * (sample - average),
+            None => sample,
+        });
+        state.requests += 1;
+        state.consecutive_failures = 0;
+        state.cooldown_until = None;
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state();
+        state.requests += 1;
+        state.failures += 1;
+        state.consecutive_failures += 1;
+        let cooldown = BASE_COOLDOWN
+            .saturating_mul(1 << (state.consecutive_failures - 1).min(5))
+            .min(MAX_COOLDOWN);
+        state.cooldown_until = Some(Instant::now() + cooldown);
+    }
+
+    async fn request<T, R>(&self, method: &str, params: &T) -> Result<R, HttpClientError>
+    where
+        T: fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let started = Instant::now();
+        let result = self.http.request(method, params).await;
+        match &result {
+            Ok(_) => self.record_success(started.elapsed()),
+            // The endpoint answered; the request itself was at fault
+            Err(e) if !is_transient(e) => self.record_success(started.elapsed()),
+            Err(_) => self.record_failure(),
+        }
+        result
+    }
+}
+
+/// JSON-RPC transport over several endpoints of one chain. Requests go to the
+/// healthiest, fastest endpoint first; transient failures are retried on the
+/// next one, with backoff between rounds. Endpoints that fail are rested for
+/// a while before being tried again.
+#[derive(Debug, Clone)]
+pub struct FailoverClient {
+    endpoints: Arc<Vec<Endpoint>>,
+}
+
+impl FailoverClient {
+    pub fn new<S: AsRef<str>>(urls: &[S]) -> anyhow::Result<Self> {
+        if urls.is_empty() {
+            return Err(anyhow::anyhow!("At least one RPC URL is required"));
+        }
+        let client = reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+
+        let mut endpoints = Vec::new();
+        for url in urls {
+            let url: reqwest::Url = url.as_ref().parse()?;
+            endpoints.push(Endpoint {
+                host: url.host_str().unwrap_or_default().to_string(),
+                http: Http::new_with_client(url, client.clone()),
+                state: Mutex::new(EndpointState::default()),
+            });
+        }
+        Ok(Self { endpoints: Arc::new(endpoints) })
+    }
+
+    pub fn provider(&self) -> RpcProvider {
+        Provider::new(self.clone())
+    }
+
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .map(|endpoint| {
+                let state = endpoint.state();
+                EndpointHealth {
+                    host: endpoint.host.clone(),
+                    healthy: state.cooldown_until.is_none_or(|until| until <= now),
+                    latency_ms: state.latency_ms,
+                    requests: state.requests,
+                    failures: state.failures,
+                    consecutive_failures: state.consecutive_failures,
+                }
+            })
+            .collect()
+    }
+
+    /// Endpoints in the order to try them: resting ones last, then by
+    /// average latency, untried ones in configured order ahead of the rest.
+    fn ranked(&self) -> Vec<&Endpoint> {
+        let now = Instant::now();
+        let mut ranked: Vec<(usize, &Endpoint)> = self.endpoints.iter().enumerate().collect();
+        ranked.sort_by(|(a_index, a), (b_index, b)| {
+            let a_key = (a.is_cooling_down(now), a.state().latency_ms.unwrap_or(0.0));
+            let b_key = (b.is_cooling_down(now), b.state().latency_ms.unwrap_or(0.0));
+            a_key
+                .0
+                .cmp(&b_key.0)
+                .then(a_key.1.total_cmp(&b_key.1))
+                .then(a_index.cmp(b_index))
+        });
+        ranked.into_iter().map(|(_, endpoint)| endpoint).collect()
+    }
+
+    /// The lowest head block among the endpoints that answer, which every
+    /// one of them can serve state for. Quorum reads are pinned to it, since
+    /// endpoints a block or two apart would otherwise disagree mid-raise.
+    pub async fn lowest_head(&self) -> Result<U64, FailoverError> {
+        let responses = join_all(
+            self.endpoints
+                .iter()
+                .map(|endpoint| endpoint.request::<_, U64>("eth_blockNumber", &())),
+        )
+        .await;
+
+        let mut lowest = None;
+        let mut last_error = None;
+        for response in responses {
+            match response {
+                Ok(head) => lowest = Some(lowest.map_or(head, |lowest: U64| lowest.min(head))),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        lowest.ok_or_else(|| FailoverError::Exhausted {
+            method: "eth_blockNumber".to_string(),
+            last: last_error.map(Box::new),
+        })
+    }
+
+    /// Sends the request to every endpoint at once and returns the answer at
+    /// least `quorum` of them agree on, for reads a decision rests on.
+    pub async fn quorum_request<T, R>(&self, method: &str, params: T, quorum: usize) -> Result<R, FailoverError>
+    where
+        T: fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let quorum = quorum.max(1);
+        if quorum > self.endpoints.len() {
+            return Err(FailoverError::NoQuorum {
+                method: method.to_string(),
+                required: quorum,
+                agreeing: 0,
+                answered: 0,
+            });
+        }
+
+        let responses = join_all(
+            self.endpoints
+                .iter()
+                .map(|endpoint| endpoint.request::<_, serde_json::Value>(method, &params)),
+        )
+        .await;
+
+        let mut answers: Vec<(serde_json::Value, usize)> = Vec::new();
+        let mut last_error = None;
+        for response in responses {
+            match response {
+                Ok(value) => match answers.iter_mut().find(|(answer, _)| *answer == value) {
+                    Some((_, count)) => *count += 1,
+                    None => answers.push((value, 1)),
+                },
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        let answered = answers.iter().map(|(_, count)| count).sum();
+        let best = answers.into_iter().max_by_key(|(_, count)| *count);
+        match best {
+            Some((value, count)) if count >= quorum => {
+                serde_json::from_value(value.clone()).map_err(|err| {
+                    FailoverError::Rpc(HttpClientError::SerdeJson { err, text: value.to_string() })
+                })
+            }
+            // Every endpoint gave the same non-transient error, e.g. a revert
+            None if last_error.as_ref().is_some_and(|e| !is_transient(e)) => {
+                Err(FailoverError::Rpc(last_error.expect("checked above")))
+            }
+            best => Err(FailoverError::NoQuorum {
+                method: method.to_string(),
+                required: quorum,
+                agreeing: best.map_or(0, |(_, count)| count),
+                answered,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for FailoverClient {
+    type Error = FailoverError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, FailoverError>
+    where
+        T: fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let mut last_error = None;
+        for round in 0..MAX_ROUNDS {
+            if round > 0 {
+                tokio::time::sleep(RETRY_BACKOFF * 2u32.pow(round - 1)).await;
+            }
+            for endpoint in self.ranked() {
+                match endpoint.request(method, &params).await {
+                    Ok(result) => return Ok(result),
+                    Err(e) if !is_transient(&e) => return Err(FailoverError::Rpc(e)),
+                    Err(e) => {
+                        println!("⚠️ RPC {} failed on {}: {}", method, endpoint.host, e);
+                        last_error = Some(e);
+                    }
+                }
+            }
+        }
+        Err(FailoverError::Exhausted {
+            method: method.to_string(),
+            last: last_error.map(Box::new),
+        })
+    }
+}
+
+/// Whether another attempt, here or on another endpoint, could succeed.
+/// Transport failures and garbled replies are; JSON-RPC errors such as a
+/// revert are not, except the ones nodes return when overloaded or behind.
+fn is_transient(error: &HttpClientError) -> bool {
+    match error {
+        HttpClientError::ReqwestError(_) | HttpClientError::SerdeJson { .. } => true,
+        HttpClientError::JsonRpcError(error) => is_transient_rpc_error(error),
+    }
+}
+
+fn is_transient_rpc_error(error: &JsonRpcError) -> bool {
+    const TRANSIENT_MESSAGES: &[&str] = &["rate limit", "too many requests", "limit exceeded", "header not found", "timeout", "busy"];
+    let message = error.message.to_lowercase();
+    error.code == -32005 || error.code == 429 || TRANSIENT_MESSAGES.iter().any(|m| message.contains(m))
+}
+
+#[derive(Debug)]
+pub enum FailoverError {
+    /// An endpoint answered with an error that retrying won't fix.
+    Rpc(HttpClientError),
+    /// Every endpoint failed on every round.
+    Exhausted { method: String, last: Option<Box<HttpClientError>> },
+    /// Too few endpoints agreed on a quorum read.
+    NoQuorum { method: String, required: usize, agreeing: usize, answered: usize },
+}
+
+impl fmt::Display for FailoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailoverError::Rpc(e) => write!(f, "{}", e),
+            FailoverError::Exhausted { method, last: Some(last) } => {
+                write!(f, "every RPC endpoint failed for {}: {}", method, last)
+            }
+            FailoverError::Exhausted { method, last: None } => write!(f, "every RPC endpoint failed for {}", method),
+            FailoverError::NoQuorum { method, required, agreeing, answered } => write!(
+                f,
+                "no quorum for {}: {} of {} answering endpoint(s) agreed, {} required",
+                method, agreeing, answered, required
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FailoverError {}
+
+impl RpcError for FailoverError {
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            FailoverError::Rpc(e) => e.as_error_response(),
+            _ => None,
+        }
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            FailoverError::Rpc(e) => e.as_serde_error(),
+            _ => None,
+        }
+    }
+}
+
+impl From<FailoverError> for ProviderError {
+    fn from(error: FailoverError) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(error))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// A JSON-RPC server on localhost answering every request with `reply`,
+    /// which gives the `result` or `error` member. Returns its URL and how
+    /// many requests it has served.
+    pub(crate) async fn stub_endpoint(reply: fn(&Value) -> Value) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let served = Arc::new(AtomicUsize::new(0));
+        let counter = served.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, reply, counter.clone()));
+            }
+        });
+        (url, served)
+    }
+
+    async fn serve(mut stream: TcpStream, reply: fn(&Value) -> Value, served: Arc<AtomicUsize>) {
+        let mut buffer = Vec::new();
+        // One connection may carry several requests
+        loop {
+            let header_end = loop {
+                if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break end + 4;
+                }
+                let mut chunk = [0u8; 4096];
+                match stream.read(&mut chunk).await {
+                    Ok(0) | Err(_) => return,
+                    Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                }
+            };
+            let headers = String::from_utf8_lossy(&buffer[..header_end]).to_lowercase();
+            let length: usize = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(0);
+            while buffer.len() < header_end + length {
+                let mut chunk = [0u8; 4096];
+                match stream.read(&mut chunk).await {
+                    Ok(0) | Err(_) => return,
+                    Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                }
+            }
+            let request: Value = serde_json::from_slice(&buffer[header_end..header_end + length]).unwrap();
+            buffer.drain(..header_end + length);
+            served.fetch_add(1, Ordering::SeqCst);
+
+            let mut response = json!({ "jsonrpc": "2.0", "id": request["id"] });
+            if let (Some(response), Value::Object(member)) = (response.as_object_mut(), reply(&request)) {
+                response.extend(member);
+            }
+            let body = response.to_string();
+            let http = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            if stream.write_all(http.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// A localhost URL nothing listens on.
+    async fn dead_endpoint() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    fn block_16(_: &Value) -> Value {
+        json!({ "result": "0x10" })
+    }
+
+    fn block_17(_: &Value) -> Value {
+        json!({ "result": "0x11" })
+    }
+
+    /// A node at block 16 or 17 whose `eth_call` answers with the block it
+    /// read state at, as a raise's total would differ between heads.
+    fn at_head_16(request: &Value) -> Value {
+        at_head(request, "0x10")
+    }
+
+    fn at_head_17(request: &Value) -> Value {
+        at_head(request, "0x11")
+    }
+
+    fn at_head(request: &Value, head: &str) -> Value {
+        match request["method"].as_str() {
+            Some("eth_call") => match request["params"][1].as_str() {
+                Some("latest") | None => json!({ "result": head }),
+                Some(block) => json!({ "result": block }),
+            },
+            _ => json!({ "result": head }),
+        }
+    }
+
+    fn reverted(_: &Value) -> Value {
+        json!({ "error": { "code": 3, "message": "execution reverted: Sale not started" } })
+    }
+
+    fn rate_limited(_: &Value) -> Value {
+        json!({ "error": { "code": -32005, "message": "rate limit exceeded" } })
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_the_next_endpoint_and_rests_the_dead_one() {
+        let dead = dead_endpoint().await;
+        let (live, served) = stub_endpoint(block_16).await;
+        let client = FailoverClient::new(&[dead, live]).unwrap();
+
+        let block: U64 = client.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, U64::from(16));
+
+        let health = client.health();
+        assert!(!health[0].healthy);
+        assert_eq!(health[0].consecutive_failures, 1);
+        assert!(health[1].healthy);
+
+        // The resting endpoint is tried last, so the next request goes straight through
+        let block: U64 = client.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, U64::from(16));
+        assert_eq!(client.health()[0].requests, 1);
+        assert_eq!(served.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_rate_limits_elsewhere() {
+        let (limited, _) = stub_endpoint(rate_limited).await;
+        let (live, _) = stub_endpoint(block_16).await;
+        let client = FailoverClient::new(&[limited, live]).unwrap();
+
+        let block: U64 = client.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, U64::from(16));
+        assert_eq!(client.health()[0].failures, 1);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_a_revert() {
+        let (reverting, _) = stub_endpoint(reverted).await;
+        let (live, served) = stub_endpoint(block_16).await;
+        let client = FailoverClient::new(&[reverting, live]).unwrap();
+
+        let error = client.request::<_, Value>("eth_call", ()).await.unwrap_err();
+        assert_eq!(error.as_error_response().unwrap().code, 3);
+        assert_eq!(served.load(Ordering::SeqCst), 0);
+        // The endpoint answered, so it is not counted as failing
+        assert!(client.health()[0].healthy);
+    }
+
+    #[tokio::test]
+    async fn gives_up_once_every_endpoint_fails() {
+        let client = FailoverClient::new(&[dead_endpoint().await]).unwrap();
+        let error = client.request::<_, U64>("eth_blockNumber", ()).await.unwrap_err();
+        assert!(matches!(error, FailoverError::Exhausted { .. }));
+    }
+
+    #[tokio::test]
+    async fn quorum_reads_need_agreement() {
+        let (first, _) = stub_endpoint(block_16).await;
+        let (second, _) = stub_endpoint(block_16).await;
+        let (third, _) = stub_endpoint(block_17).await;
+        let client = FailoverClient::new(&[first, second, third]).unwrap();
+
+        let block: U64 = client.quorum_request("eth_blockNumber", (), 2).await.unwrap();
+        assert_eq!(block, U64::from(16));
+
+        let error = client.quorum_request::<_, U64>("eth_blockNumber", (), 3).await.unwrap_err();
+        assert!(matches!(error, FailoverError::NoQuorum { agreeing: 2, answered: 3, .. }));
+    }
+
+    #[tokio::test]
+    async fn lowest_head_skips_endpoints_that_fail() {
+        let (ahead, _) = stub_endpoint(block_17).await;
+        let (behind, _) = stub_endpoint(block_16).await;
+        let client = FailoverClient::new(&[ahead, dead_endpoint().await, behind]).unwrap();
+        assert_eq!(client.lowest_head().await.unwrap(), U64::from(16));
+
+        let client = FailoverClient::new(&[dead_endpoint().await]).unwrap();
+        assert!(matches!(client.lowest_head().await, Err(FailoverError::Exhausted { .. })));
+    }
+
+    #[tokio::test]
+    async fn quorum_calls_agree_once_pinned_to_the_lowest_head() {
+        let (behind, _) = stub_endpoint(at_head_16).await;
+        let (ahead, _) = stub_endpoint(at_head_17).await;
+        let client = FailoverClient::new(&[behind, ahead]).unwrap();
+
+        let unpinned = client.quorum_request::<_, Value>("eth_call", (json!({}), "latest"), 2).await;
+        assert!(matches!(unpinned, Err(FailoverError::NoQuorum { .. })));
+
+        let head = client.lowest_head().await.unwrap();
+        let pinned: U64 = client.quorum_request("eth_call", (json!({}), head), 2).await.unwrap();
+        assert_eq!(pinned, U64::from(16));
+    }
+}
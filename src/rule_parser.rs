@@ -0,0 +1,554 @@
+use crate::launchpad_services::ProjectStatus;
+use crate::slots::IntentDraft;
+use crate::units::{self, Amount};
+use ethers::types::U256;
+use serde_json::{json, Value};
+
+/// A deterministic parse of a user message. `confident` is set only for
+/// short, single-purpose commands where every parameter was found and
+/// nothing conflicts, so the LLM can be skipped entirely. Parameters that
+/// could not be found are left out of the draft.
+#[derive(Debug, Clone)]
+pub struct RuleParse {
+    pub draft: IntentDraft,
+    pub confident: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    Create,
+    Invest,
+    Claim,
+    Refund,
+    Complete,
+    Info,
+    Analytics,
+    List,
+    Balance,
+    Investments,
+    Stats,
+    Search,
+    Trending,
+    Newest,
+    ByStatus,
+}
+
+const COMMAND_WORDS: &[(&str, Command)] = &[
+    ("create", Command::Create),
+    ("launch", Command::Create),
+    ("deploy", Command::Create),
+    ("invest", Command::Invest),
+    ("contribute", Command::Invest),
+    ("claim", Command::Claim),
+    ("refund", Command::Refund),
+    ("complete", Command::Complete),
+    ("finalize", Command::Complete),
+    ("finalise", Command::Complete),
+    ("info", Command::Info),
+    ("details", Command::Info),
+    ("about", Command::Info),
+    ("analytics", Command::Analytics),
+    ("velocity", Command::Analytics),
+    ("momentum", Command::Analytics),
+    ("performance", Command::Analytics),
+    ("list", Command::List),
+    ("projects", Command::List),
+    ("balance", Command::Balance),
+    ("investments", Command::Investments),
+    ("portfolio", Command::Investments),
+    ("holdings", Command::Investments),
+    ("stats", Command::Stats),
+    ("statistics", Command::Stats),
+    ("search", Command::Search),
+    ("find", Command::Search),
+    ("lookup", Command::Search),
+    ("trending", Command::Trending),
+    ("hot", Command::Trending),
+    ("popular", Command::Trending),
+    ("new", Command::Newest),
+    ("newest", Command::Newest),
+    ("latest", Command::Newest),
+    ("recent", Command::Newest),
+    ("newly", Command::Newest),
+];
+
+/// Words that may surround "more" in a request for the next page of results.
+const MORE_WORDS: &[&str] = &["more", "next", "page", "show", "load", "see", "me", "the", "results", "please", "projects"];
+
+/// Words that make an otherwise clean command worth a second opinion.
+const HEDGE_WORDS: &[&str] = &["not", "don't", "dont", "never", "maybe", "should", "cancel", "why", "how", "what", "if"];
+
+/// Longest message still treated as a terse command.
+const MAX_COMMAND_WORDS: usize = 12;
+
+pub fn parse_intent(input: &str) -> Option<RuleParse> {
+    let tokens = tokenize(input);
+    if tokens.is_empty() {
+        return None;
+    }
+    let lower: Vec<String> = tokens.iter().map(|t| t.to_lowercase()).collect();
+
+    let mut commands: Vec<Command> = Vec::new();
+    for word in &lower {
+        if let Some((_, command)) = COMMAND_WORDS.iter().find(|(w, _)| w == word) {
+            if !commands.contains(command) {
+                commands.push(*command);
+            }
+        }
+    }
+    // "failed projects", "show active projects": a status word next to talk of
+    // projects, but not "refund from the failed project 0x..."
+    let mentions_projects = lower.iter().any(|w| w == "project" || w == "projects")
+        && !lower.iter().any(|w| w.starts_with("0x"));
+    let status = match find_statuses(&lower).as_slice() {
+        [status] if mentions_projects => Some(*status),
+        _ => None,
+    };
+    if status.is_some() {
+        commands.push(Command::ByStatus);
+    }
+    // "create a new project" is a creation, not a listing of new projects
+    if commands.contains(&Command::Create) {
+        commands.retain(|c| *c != Command::Newest);
+    }
+    // "projects" on its own means list, but not in "invest in projects", "project
+    // info" or "trending projects"
+    let discovery = commands
+        .iter()
+        .any(|c| matches!(c, Command::Search | Command::Trending | Command::Newest | Command::ByStatus));
+    if commands.len() > 1 && (discovery || !lower.iter().any(|w| w == "list")) {
+        commands.retain(|c| *c != Command::List);
+    }
+    // "analytics stats for PEPE" is about one project, not the whole platform
+    if commands.contains(&Command::Analytics) {
+        commands.retain(|c| *c != Command::Stats);
+    }
+    // "claim a refund" is a refund, not a token claim
+    if commands.contains(&Command::Refund) {
+        commands.retain(|c| *c != Command::Claim);
+    }
+    let command = match commands.as_slice() {
+        [command] => *command,
+        _ => return None,
+    };
+
+    let addresses = find_addresses(&tokens);
+    let amounts = find_amounts(&lower);
+    let hedged = lower.iter().any(|w| HEDGE_WORDS.contains(&w.as_str())) || input.contains('?');
+    let terse = tokens.len() <= MAX_COMMAND_WORDS && !hedged;
+
+    // Without an address, whatever is left after the command, amount and
+    // filler words names the project: "invest 0.5 in PEPE"
+    let single_address = match addresses.as_slice() {
+        [address] => Some(Value::String(address.clone())),
+        [] => project_reference(&tokens, &lower).map(Value::String),
+        _ => None,
+    };
+    let single_amount = match amounts.as_slice() {
+        [amount] => Some(json!(Amount::Exact(*amount))),
+        // "invest all in PEPE"
+        [] if lower.iter().any(|w| units::is_all_word(w)) => Some(json!(Amount::All)),
+        _ => None,
+    };
+    let with_slots = |intent: &str, slots: Vec<(&str, Option<Value>)>| {
+        let mut draft = IntentDraft::new(intent);
+        let mut complete = true;
+        for (name, value) in slots {
+            match value {
+                Some(value) => draft = draft.with_slot(name, value),
+                None => complete = false,
+            }
+        }
+        (draft, complete)
+    };
+
+    let (draft, complete) = match command {
+        Command::Invest => with_slots("Invest", vec![("project_id", single_address), ("amount", single_amount)]),
+        Command::Claim => {
+            let (draft, complete) = with_slots("ClaimTokens", vec![("project_id", single_address)]);
+            (draft, complete && amounts.is_empty())
+        }
+        Command::Refund => {
+            let (draft, complete) = with_slots("ClaimRefund", vec![("project_id", single_address)]);
+            (draft, complete && amounts.is_empty())
+        }
+        Command::Complete => {
+            let (draft, complete) = with_slots("CompleteProject", vec![("project_id", single_address)]);
+            (draft, complete && amounts.is_empty())
+        }
+        Command::Info => {
+            let (draft, complete) = with_slots("GetProjectInfo", vec![("project_id", single_address)]);
+            (draft, complete && amounts.is_empty())
+        }
+        Command::Analytics => {
+            let (draft, complete) = with_slots("GetProjectAnalytics", vec![("project_id", single_address)]);
+            (draft, complete && amounts.is_empty())
+        }
+        Command::Create => {
+            let (name, symbol) = parse_create(&tokens, &lower);
+            let (draft, complete) = with_slots(
+                "CreateProject",
+                vec![("name", name.map(Value::String)), ("symbol", symbol.map(Value::String))],
+            );
+            (draft, complete && addresses.is_empty())
+        }
+        Command::List => (IntentDraft::new("ListProjects"), addresses.is_empty()),
+        // "token balance of PEPE", "balance of 0x...": a token held, not the native balance
+        Command::Balance if !addresses.is_empty() || lower.iter().any(|w| w == "token" || w == "tokens") => {
+            let (draft, complete) = with_slots("GetTokenBalance", vec![("project_id", single_address)]);
+            (draft, complete && amounts.is_empty())
+        }
+        Command::Balance => (IntentDraft::new("GetUserBalance"), addresses.is_empty()),
+        Command::Investments => (IntentDraft::new("MyInvestments"), addresses.is_empty()),
+        Command::Stats => (IntentDraft::new("GetProjectStatistics"), addresses.is_empty()),
+        Command::Search => {
+            let query = search_query(&tokens, &lower);
+            let (draft, complete) = with_slots("SearchProjects", vec![("query", query.map(Value::String))]);
+            (draft, complete && addresses.is_empty())
+        }
+        Command::Trending | Command::Newest => {
+            let intent = if command == Command::Trending { "TrendingProjects" } else { "NewProjects" };
+            let mut draft = IntentDraft::new(intent);
+            let counts: Vec<u64> = lower.iter().filter_map(|w| w.parse().ok()).collect();
+            if let [limit] = counts.as_slice() {
+                draft = draft.with_slot("limit", json!(limit));
+            }
+            (draft, amounts.len() <= 1 && addresses.is_empty())
+        }
+        Command::ByStatus => {
+            let mut draft = IntentDraft::new("ProjectsByStatus");
+            if let Some(status) = status {
+                draft = draft.with_slot("status", json!(status));
+            }
+            let page = lower
+                .iter()
+                .position(|w| w == "page")
+                .and_then(|i| lower.get(i + 1))
+                .and_then(|w| w.parse::<u64>().ok());
+            if let Some(page) = page {
+                draft = draft.with_slot("page", json!(page));
+            }
+            (draft, addresses.is_empty())
+        }
+    };
+
+    Some(RuleParse {
+        draft,
+        confident: complete && terse,
+    })
+}
+
+/// Whether the message only asks for the next page of the last listing,
+/// e.g. "more", "next page" or "show me more".
+pub fn is_more_request(input: &str) -> bool {
+    let lower: Vec<String> = tokenize(input).iter().map(|t| t.to_lowercase()).collect();
+    lower.iter().any(|w| w == "more" || w == "next")
+        && lower.iter().all(|w| MORE_WORDS.contains(&w.as_str()))
+}
+
+/// A 1-based position from replies like "2", "#2", "2nd" or "the second one".
+pub fn find_ordinal(text: &str) -> Option<usize> {
+    const ORDINALS: &[&str] = &["first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth"];
+    let lower: Vec<String> = tokenize(text).iter().map(|t| t.to_lowercase()).collect();
+    let positions: Vec<usize> = lower
+        .iter()
+        .filter_map(|word| {
+            let word = word.trim_start_matches('#');
+            let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            let suffix = &word[digits.len()..];
+            if !digits.is_empty() && matches!(suffix, "" | "st" | "nd" | "rd" | "th") {
+                digits.parse().ok()
+            } else {
+                ORDINALS.iter().position(|o| *o == word).map(|i| i + 1)
+            }
+        })
+        .collect();
+    match positions.as_slice() {
+        [position] if *position > 0 => Some(*position),
+        _ => None,
+    }
+}
+
+/// Every distinct 0x address in free text.
+pub fn find_addresses_in(text: &str) -> Vec<String> {
+    find_addresses(&tokenize(text))
+}
+
+/// Every amount in free text, in wei.
+pub fn find_amounts_in(text: &str) -> Vec<U256> {
+    let lower: Vec<String> = tokenize(text).iter().map(|t| t.to_lowercase()).collect();
+    find_amounts(&lower)
+}
+
+fn tokenize(input: &str) -> Vec<String> {
+    input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|t| t.trim_matches(|c: char| matches!(c, '.' | '!' | '?' | ':' | ';' | '"' | '\'')))
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn find_addresses(tokens: &[String]) -> Vec<String> {
+    let mut addresses: Vec<String> = Vec::new();
+    for token in tokens {
+        let token = token.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+        let is_address = token.len() == 42
+            && (token.starts_with("0x") || token.starts_with("0X"))
+            && token[2..].chars().all(|c| c.is_ascii_hexdigit());
+        if is_address && !addresses.iter().any(|a| a.eq_ignore_ascii_case(token)) {
+            addresses.push(token.to_string());
+        }
+    }
+    addresses
+}
+
+/// Amounts in wei. Accepts "0.5", "0.5 BNB", "0.5bnb", "1.5k" and
+/// "500 finney"; bare numbers count as native units.
+fn find_amounts(lower: &[String]) -> Vec<U256> {
+    let mut amounts = Vec::new();
+    let mut i = 0;
+    while i < lower.len() {
+        let token = &lower[i];
+        if token.starts_with("0x") {
+            i += 1;
+            continue;
+        }
+
+        let unit = lower
+            .get(i + 1)
+            .map(String::as_str)
+            .filter(|next| units::native_unit_decimals(next).is_some());
+        if let Some(Ok(wei)) = units::parse_native(token, unit) {
+            amounts.push(wei);
+            // The next word is this amount's unit, unless one was glued on
+            let glued = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+            if unit.is_some() && units::native_unit_decimals(glued).is_none() {
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+    amounts
+}
+
+/// Extracts the name and symbol from phrasings like "create MyToken with
+/// symbol MTK", "launch a token called Moon Cat (MCAT)" or "create project Foo
+/// $FOO". Either part is `None` when it can't be found unambiguously.
+fn parse_create(tokens: &[String], lower: &[String]) -> (Option<String>, Option<String>) {
+    let mut symbols: Vec<(usize, String)> = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let candidate = if token.starts_with('(') && token.ends_with(')') {
+            Some(token.trim_matches(|c| c == '(' || c == ')'))
+        } else if let Some(rest) = token.strip_prefix('$') {
+            Some(rest)
+        } else if i > 0 && matches!(lower[i - 1].as_str(), "symbol" | "ticker") {
+            Some(token.as_str())
+        } else {
+            None
+        };
+        if let Some(candidate) = candidate.filter(|c| is_symbol(c)) {
+            symbols.push((i, candidate.to_uppercase()));
+        }
+    }
+    let (symbol_index, symbol) = match symbols.as_slice() {
+        [(i, symbol)] => (*i, Some(symbol.clone())),
+        [] => (tokens.len(), None),
+        _ => return (None, None),
+    };
+
+    // Name runs from after the command/filler words up to the symbol clause
+    let fillers = ["create", "launch", "deploy", "a", "an", "new", "project", "token", "called", "named"];
+    let Some(start) = lower.iter().position(|w| !fillers.contains(&w.as_str())) else {
+        return (None, symbol);
+    };
+    let end = lower[start..]
+        .iter()
+        .position(|w| matches!(w.as_str(), "with" | "symbol" | "ticker" | "and"))
+        .map(|p| p + start)
+        .unwrap_or(symbol_index)
+        .min(symbol_index);
+
+    let name = (start < end).then(|| tokens[start..end].join(" "));
+    (name, symbol)
+}
+
+fn find_statuses(lower: &[String]) -> Vec<ProjectStatus> {
+    let mut statuses: Vec<ProjectStatus> = Vec::new();
+    for status in lower.iter().filter_map(|w| ProjectStatus::from_name(w)) {
+        if !statuses.contains(&status) {
+            statuses.push(status);
+        }
+    }
+    statuses
+}
+
+/// The search term after "search"/"find", without filler words: "find
+/// projects called Moon Cat" searches for "Moon Cat".
+fn search_query(tokens: &[String], lower: &[String]) -> Option<String> {
+    let start = lower
+        .iter()
+        .position(|w| matches!(COMMAND_WORDS.iter().find(|(c, _)| c == w), Some((_, Command::Search))))?
+        + 1;
+    let fillers = ["for", "a", "an", "the", "project", "projects", "token", "tokens", "called", "named", "me"];
+    let words: Vec<&str> = tokens[start..]
+        .iter()
+        .zip(&lower[start..])
+        .skip_while(|(_, w)| fillers.contains(&w.as_str()))
+        .map(|(t, _)| t.trim_start_matches('$'))
+        .filter(|t| !t.is_empty())
+        .collect();
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+/// Words left once command words, amounts, units and fillers are removed,
+/// taken as a project name or symbol. Long leftovers are more likely a
+/// sentence than a name, so they are not used.
+fn project_reference(tokens: &[String], lower: &[String]) -> Option<String> {
+    const FILLERS: &[&str] = &[
+        "in", "into", "to", "from", "for", "of", "on", "the", "a", "an", "my", "me", "please", "project", "token",
+        "tokens", "some", "worth", "with", "i", "want", "would", "like", "get", "show", "called", "named", "now",
+        "check",
+    ];
+    const MAX_REFERENCE_WORDS: usize = 3;
+
+    let words: Vec<&str> = tokens
+        .iter()
+        .zip(lower)
+        .filter(|(_, w)| !COMMAND_WORDS.iter().any(|(c, _)| c == w))
+        .filter(|(_, w)| !FILLERS.contains(&w.as_str()) && units::native_unit_decimals(w).is_none())
+        .filter(|(_, w)| !units::is_all_word(w) && units::parse_native(w, None).is_none())
+        .map(|(t, _)| t.trim_start_matches('$'))
+        .filter(|t| !t.is_empty())
+        .collect();
+    (!words.is_empty() && words.len() <= MAX_REFERENCE_WORDS).then(|| words.join(" "))
+}
+
+fn is_symbol(candidate: &str) -> bool {
+    (2..=10).contains(&candidate.len()) && candidate.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "0x1111111111111111111111111111111111111111";
+
+    fn parse(input: &str) -> RuleParse {
+        parse_intent(input).unwrap_or_else(|| panic!("no rule parse for {:?}", input))
+    }
+
+    fn slot<'a>(parse: &'a RuleParse, name: &str) -> Option<&'a Value> {
+        parse.draft.slots.get(name)
+    }
+
+    #[test]
+    fn invest_with_address_and_amount_is_confident() {
+        let parsed = parse(&format!("invest 0.5 BNB in {}", ADDRESS));
+        assert_eq!(parsed.draft.intent, "Invest");
+        assert_eq!(slot(&parsed, "project_id"), Some(&json!(ADDRESS)));
+        assert_eq!(slot(&parsed, "amount"), Some(&json!("0.5")));
+        assert!(parsed.confident);
+    }
+
+    #[test]
+    fn invest_takes_a_project_name_and_all() {
+        let parsed = parse("invest all in PEPE");
+        assert_eq!(slot(&parsed, "project_id"), Some(&json!("PEPE")));
+        assert_eq!(slot(&parsed, "amount"), Some(&json!("all")));
+    }
+
+    #[test]
+    fn buying_tokens_is_left_to_the_llm() {
+        // "100 PEPE" is a token amount, not 100 of the native currency
+        assert!(parse_intent("buy 100 PEPE").is_none_or(|parsed| !parsed.confident));
+    }
+
+    #[test]
+    fn invest_without_amount_is_incomplete() {
+        let parsed = parse(&format!("invest in {}", ADDRESS));
+        assert_eq!(slot(&parsed, "amount"), None);
+        assert!(!parsed.confident);
+    }
+
+    #[test]
+    fn hedged_messages_are_not_confident() {
+        assert!(!parse(&format!("should I invest 0.5 in {}", ADDRESS)).confident);
+        assert!(!parse(&format!("invest 0.5 in {}?", ADDRESS)).confident);
+    }
+
+    #[test]
+    fn refund_wins_over_claim() {
+        let parsed = parse(&format!("claim a refund from {}", ADDRESS));
+        assert_eq!(parsed.draft.intent, "ClaimRefund");
+    }
+
+    #[test]
+    fn create_reads_name_and_symbol() {
+        let parsed = parse("create Moon Cat with symbol mcat");
+        assert_eq!(parsed.draft.intent, "CreateProject");
+        assert_eq!(slot(&parsed, "name"), Some(&json!("Moon Cat")));
+        assert_eq!(slot(&parsed, "symbol"), Some(&json!("MCAT")));
+
+        // "new" names no listing here
+        let parsed = parse("create a new project called Foo $FOO");
+        assert_eq!(parsed.draft.intent, "CreateProject");
+        assert_eq!(slot(&parsed, "name"), Some(&json!("Foo")));
+        assert_eq!(slot(&parsed, "symbol"), Some(&json!("FOO")));
+    }
+
+    #[test]
+    fn discovery_commands_take_limits_and_statuses() {
+        let parsed = parse("trending 5");
+        assert_eq!(parsed.draft.intent, "TrendingProjects");
+        assert_eq!(slot(&parsed, "limit"), Some(&json!(5)));
+
+        let parsed = parse("show failed projects");
+        assert_eq!(parsed.draft.intent, "ProjectsByStatus");
+        assert!(slot(&parsed, "status").is_some());
+    }
+
+    #[test]
+    fn balance_of_a_token_is_a_token_balance() {
+        assert_eq!(parse("my balance").draft.intent, "GetUserBalance");
+
+        let parsed = parse("check my PEPE token balance");
+        assert_eq!(parsed.draft.intent, "GetTokenBalance");
+        assert_eq!(slot(&parsed, "project_id"), Some(&json!("PEPE")));
+
+        let parsed = parse(&format!("balance of {}", ADDRESS));
+        assert_eq!(parsed.draft.intent, "GetTokenBalance");
+        assert_eq!(slot(&parsed, "project_id"), Some(&json!(ADDRESS)));
+    }
+
+    #[test]
+    fn conflicting_commands_are_left_to_the_llm() {
+        assert!(parse_intent(&format!("invest in and claim from {}", ADDRESS)).is_none());
+        assert!(parse_intent("hello there").is_none());
+    }
+
+    #[test]
+    fn finds_amounts_with_units() {
+        let amounts = find_amounts_in("send 0.5 BNB and 500 gwei, then 1.5k");
+        assert_eq!(
+            amounts,
+            vec![
+                U256::exp10(17) * 5,
+                U256::from(500) * U256::exp10(9),
+                U256::from(1500) * U256::exp10(18),
+            ]
+        );
+    }
+
+    #[test]
+    fn more_requests_and_ordinals() {
+        assert!(is_more_request("show me more"));
+        assert!(is_more_request("next page"));
+        assert!(!is_more_request("more about PEPE"));
+        assert_eq!(find_ordinal("the second one"), Some(2));
+        assert_eq!(find_ordinal("#3"), Some(3));
+        assert_eq!(find_ordinal("1st"), Some(1));
+        assert_eq!(find_ordinal("1 or 2"), None);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::launchpad_services::MECHALAUNCHPAD_ABI;
+use crate::rpc::RpcProvider;
+use ethers::abi::{AbiDecode, ErrorExt, Token};
+use ethers::providers::{Middleware, ProviderError, RpcError};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Bytes, U256};
+use ethers::utils::hex;
+use serde::{Deserialize, Serialize};
+
+/// Selector of Solidity's `Error(string)`, raised by `require` and `revert("...")`.
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of `Panic(uint256)`, raised by failed asserts and arithmetic checks.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationVerdict {
+    /// The call succeeds against the latest block.
+    Succeeds,
+    /// The call reverts against the latest block.
+    Reverts,
+    /// The node could not be asked, or there was no wallet to simulate from.
+    Unknown,
+}
+
+/// Outcome of running a prepared transaction against the latest block
+/// before the user is asked to sign it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Simulation {
+    pub verdict: SimulationVerdict,
+    /// Why the call reverts, or why it could not be simulated.
+    pub reason: Option<String>,
+    /// Gas the call used, from `eth_estimateGas`.
+    pub gas_used: Option<String>,
+    /// Sender the call was simulated from.
+    pub from: Option<String>,
+}
+
+impl Simulation {
+    pub fn unknown(reason: impl Into<String>) -> Self {
+        Self {
+            verdict: SimulationVerdict::Unknown,
+            reason: Some(reason.into()),
+            gas_used: None,
+            from: None,
+        }
+    }
+
+    pub fn reverts(&self) -> bool {
+        self.verdict == SimulationVerdict::Reverts
+    }
+}
+
+/// Runs `tx` through `eth_call` and `eth_estimateGas`. A revert from either
+/// is decoded into a readable reason; any other failure leaves the verdict
+/// unknown.
+pub async fn simulate(provider: &RpcProvider, tx: &TypedTransaction) -> Simulation {
+    let from = tx.from().map(|from| format!("{:?}", from));
+
+    let outcome = match provider.call(tx, None).await {
+        Ok(_) => provider.estimate_gas(tx, None).await.map(Some),
+        Err(e) => Err(e),
+    };
+    let simulation = match outcome {
+        Ok(gas_used) => Simulation {
+            verdict: SimulationVerdict::Succeeds,
+            reason: None,
+            gas_used: gas_used.map(|gas| gas.to_string()),
+            from: None,
+        },
+        Err(e) => match revert_reason(&e) {
+            Some(reason) => Simulation {
+                verdict: SimulationVerdict::Reverts,
+                reason: Some(reason),
+                gas_used: None,
+                from: None,
+            },
+            None => Simulation::unknown(format!("Could not simulate the transaction: {}", e)),
+        },
+    };
+    Simulation { from, ..simulation }
+}
+
+/// The revert reason carried by a failed `eth_call` or `eth_estimateGas`,
+/// or `None` when the request failed for another reason.
+fn revert_reason(error: &ProviderError) -> Option<String> {
+    let response = error.as_error_response()?;
+    match response.as_revert_data() {
+        Some(data) if !data.is_empty() => return Some(decode_revert(&data)),
+        Some(_) => {}
+        None if response.message.to_lowercase().contains("revert") => {}
+        None => return None,
+    }
+    // Some nodes put the reason in the message instead of returning the data
+    let reason = response
+        .message
+        .split_once("reverted: ")
+        .map(|(_, reason)| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    Some(reason.unwrap_or_else(|| "The transaction reverted without a reason".to_string()))
+}
+
+/// Readable form of revert data: `Error(string)`, `Panic(uint256)`, or a
+/// custom error declared in the `MechaLaunchpad` ABI.
+pub fn decode_revert(data: &Bytes) -> String {
+    if data.is_empty() {
+        return "The transaction reverted without a reason".to_string();
+    }
+    if data.len() < 4 {
+        return format!("The transaction reverted with malformed data {}", data);
+    }
+    let (selector, args) = data.split_at(4);
+
+    if selector == ERROR_STRING_SELECTOR {
+        if let Ok(reason) = String::decode(args) {
+            return reason;
+        }
+    }
+    if selector == PANIC_SELECTOR {
+        if let Ok(code) = U256::decode(args) {
+            return panic_reason(code);
+        }
+    }
+
+    let custom = MECHALAUNCHPAD_ABI
+        .errors()
+        .find(|error| error.selector().as_slice() == selector);
+    match custom {
+        Some(error) => match error.decode(args) {
+            Ok(tokens) if tokens.is_empty() => error.name.clone(),
+            Ok(tokens) => {
+                let args: Vec<String> = tokens.iter().map(format_token).collect();
+                format!("{}({})", error.name, args.join(", "))
+            }
+            Err(_) => error.name.clone(),
+        },
+        None => format!("The transaction reverted with unknown error 0x{}", hex::encode(selector)),
+    }
+}
+
+/// What the compiler-inserted panic codes mean.
+fn panic_reason(code: U256) -> String {
+    let reason = match code.low_u64() {
+        0x01 => "an assertion failed",
+        0x11 => "an arithmetic operation overflowed",
+        0x12 => "division by zero",
+        0x21 => "an invalid enum value",
+        0x22 => "corrupted storage",
+        0x31 => "pop on an empty array",
+        0x32 => "an array index out of bounds",
+        0x41 => "too much memory allocated",
+        0x51 => "a call to an uninitialized function",
+        _ => return format!("The contract panicked with code 0x{:x}", code),
+    };
+    format!("The contract panicked: {}", reason)
+}
+
+fn format_token(token: &Token) -> String {
+    match token {
+        Token::Address(address) => format!("{:?}", address),
+        Token::Uint(value) | Token::Int(value) => value.to_string(),
+        Token::String(value) => value.clone(),
+        Token::Bool(value) => value.to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::encode;
+
+    fn revert_data(selector: [u8; 4], args: &[Token]) -> Bytes {
+        let mut data = selector.to_vec();
+        data.extend(encode(args));
+        data.into()
+    }
+
+    #[test]
+    fn decodes_error_string() {
+        let data = revert_data(ERROR_STRING_SELECTOR, &[Token::String("Sale not started".to_string())]);
+        assert_eq!(decode_revert(&data), "Sale not started");
+    }
+
+    #[test]
+    fn decodes_panic_codes() {
+        let data = revert_data(PANIC_SELECTOR, &[Token::Uint(U256::from(0x11))]);
+        assert_eq!(decode_revert(&data), "The contract panicked: an arithmetic operation overflowed");
+        let data = revert_data(PANIC_SELECTOR, &[Token::Uint(U256::from(0x99))]);
+        assert_eq!(decode_revert(&data), "The contract panicked with code 0x99");
+    }
+
+    #[test]
+    fn names_unknown_selectors() {
+        let data = revert_data([0xde, 0xad, 0xbe, 0xef], &[]);
+        assert_eq!(decode_revert(&data), "The transaction reverted with unknown error 0xdeadbeef");
+    }
+
+    #[test]
+    fn handles_empty_and_short_data() {
+        assert_eq!(decode_revert(&Bytes::new()), "The transaction reverted without a reason");
+        assert!(decode_revert(&Bytes::from(vec![0x08, 0xc3])).contains("malformed"));
+    }
+}
@@ -0,0 +1,566 @@
+use crate::agent::Intent;
+use crate::launchpad_services::ProjectStatus;
+use crate::rule_parser;
+use crate::units::Amount;
+use ethers::types::Address;
+use ethers::utils::to_checksum;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::str::FromStr;
+
+/// An intent whose parameters may still be missing or invalid. Slot names
+/// match the field names of the corresponding `Intent` variant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentDraft {
+    pub intent: String,
+    pub slots: Map<String, Value>,
+}
+
+impl IntentDraft {
+    pub fn new(intent: &str) -> Self {
+        Self { intent: intent.to_string(), slots: Map::new() }
+    }
+
+    pub fn unknown() -> Self {
+        Self::new("Unknown")
+    }
+
+    /// The draft a resolved intent came from, e.g. to ask about one of its slots again.
+    pub fn from_intent(intent: &Intent) -> Self {
+        match serde_json::to_value(intent) {
+            Ok(Value::Object(tagged)) => match tagged.into_iter().next() {
+                Some((name, Value::Object(slots))) => Self { intent: name, slots },
+                Some((name, _)) => Self::new(&name),
+                None => Self::unknown(),
+            },
+            Ok(Value::String(name)) => Self::new(&name),
+            _ => Self::unknown(),
+        }
+    }
+
+    pub fn with_slot(mut self, name: &str, value: Value) -> Self {
+        self.slots.insert(name.to_string(), value);
+        self
+    }
+
+    /// Copies every slot from `other` that is set there, keeping ours otherwise.
+    pub fn merge(&mut self, other: &IntentDraft) {
+        for (name, value) in &other.slots {
+            if !value.is_null() {
+                self.slots.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidSlot {
+    pub field: String,
+    pub value: Value,
+    pub reason: String,
+}
+
+/// One of several values the user can pick from, e.g. projects sharing a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Choice {
+    pub value: String,
+    pub label: String,
+}
+
+/// What the agent needs from the user before it can act on a draft.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Clarification {
+    pub intent: String,
+    pub question: String,
+    pub missing_fields: Vec<String>,
+    pub invalid_fields: Vec<InvalidSlot>,
+    pub draft: IntentDraft,
+    /// Candidates for the open slot, in the order they were offered.
+    #[serde(default)]
+    pub choices: Vec<Choice>,
+}
+
+impl Clarification {
+    /// Slots the next user turn should fill, in the order they were asked for.
+    pub fn open_fields(&self) -> Vec<String> {
+        let mut fields = self.missing_fields.clone();
+        fields.extend(self.invalid_fields.iter().map(|slot| slot.field.clone()));
+        fields
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SlotKind {
+    /// A project address, or a name/symbol the agent resolves to one.
+    Project,
+    /// A contract address, taken as is.
+    Token,
+    Amount,
+    Symbol,
+    Name,
+    Query,
+    Count,
+    Status,
+    Cursor,
+}
+
+struct SlotSpec {
+    name: &'static str,
+    kind: SlotKind,
+    /// Optional slots are never asked for; the intent's serde default applies.
+    required: bool,
+    question: &'static str,
+}
+
+const MAX_NAME_LEN: usize = 50;
+const MAX_QUERY_LEN: usize = 64;
+/// Largest trending/newest listing a user can ask for in one go.
+pub const MAX_LISTING_LIMIT: u64 = 50;
+const MIN_SYMBOL_LEN: usize = 2;
+const MAX_SYMBOL_LEN: usize = 10;
+
+fn slot_specs(intent: &str) -> &'static [SlotSpec] {
+    match intent {
+        "CreateProject" => &[
+            SlotSpec { name: "name", kind: SlotKind::Name, required: true, question: "What should the project be called?" },
+            SlotSpec { name: "symbol", kind: SlotKind::Symbol, required: true, question: "What ticker symbol should the token use (2-10 letters or digits)?" },
+        ],
+        "Invest" => &[
+            SlotSpec { name: "project_id", kind: SlotKind::Project, required: true, question: "Which project would you like to invest in? You can give its name, symbol or contract address." },
+            SlotSpec { name: "amount", kind: SlotKind::Amount, required: true, question: "How much would you like to invest (e.g. 0.5 BNB)?" },
+        ],
+        "ClaimTokens" => &[
+            SlotSpec { name: "project_id", kind: SlotKind::Project, required: true, question: "Which project do you want to claim tokens from? You can give its name, symbol or contract address." },
+        ],
+        "ClaimRefund" => &[
+            SlotSpec { name: "project_id", kind: SlotKind::Project, required: true, question: "Which project do you want a refund from? You can give its name, symbol or contract address." },
+        ],
+        "CompleteProject" => &[
+            SlotSpec { name: "project_id", kind: SlotKind::Project, required: true, question: "Which project should be completed? You can give its name, symbol or contract address." },
+        ],
+        "SearchProjects" => &[
+            SlotSpec { name: "query", kind: SlotKind::Query, required: true, question: "What name or symbol should I search for?" },
+            SlotSpec { name: "cursor", kind: SlotKind::Cursor, required: false, question: "" },
+        ],
+        "TrendingProjects" | "NewProjects" => &[
+            SlotSpec { name: "limit", kind: SlotKind::Count, required: false, question: "" },
+            SlotSpec { name: "cursor", kind: SlotKind::Cursor, required: false, question: "" },
+        ],
+        "ProjectsByStatus" => &[
+            SlotSpec { name: "status", kind: SlotKind::Status, required: true, question: "Which status are you interested in (pending, approved, rejected, active, successful, failed or completed)?" },
+            SlotSpec { name: "page", kind: SlotKind::Count, required: false, question: "" },
+        ],
+        "GetProjectInfo" => &[
+            SlotSpec { name: "project_id", kind: SlotKind::Project, required: true, question: "Which project would you like to look up? You can give its name, symbol or contract address." },
+        ],
+        "GetProjectAnalytics" => &[
+            SlotSpec { name: "project_id", kind: SlotKind::Project, required: true, question: "Which project's analytics would you like to see? You can give its name, symbol or contract address." },
+        ],
+        "GetTokenBalance" => &[
+            SlotSpec { name: "token", kind: SlotKind::Token, required: false, question: "" },
+            SlotSpec { name: "project_id", kind: SlotKind::Project, required: false, question: "Which token would you like to check? You can give the token's contract address, or the name, symbol or address of the project offering it." },
+        ],
+        _ => &[],
+    }
+}
+
+/// Slots of which an intent needs at least one, though each is optional on
+/// its own. The last is asked for when none is set.
+fn alternative_slots(intent: &str) -> &'static [&'static str] {
+    match intent {
+        "GetTokenBalance" => &["token", "project_id"],
+        _ => &[],
+    }
+}
+
+/// Validates every required slot of `draft`, normalizing values (checksummed
+/// addresses, upper-case symbols). Returns the finished `Intent`, or what to
+/// ask the user when a slot is missing or invalid.
+pub fn resolve(draft: IntentDraft) -> Result<Intent, Box<Clarification>> {
+    let specs = slot_specs(&draft.intent);
+    let mut normalized = Map::new();
+    let mut missing_fields = Vec::new();
+    let mut invalid_fields = Vec::new();
+
+    for spec in specs {
+        match draft.slots.get(spec.name).filter(|v| !v.is_null()) {
+            None if spec.required => missing_fields.push(spec.name.to_string()),
+            None => {}
+            Some(value) => match validate_slot(spec.kind, value) {
+                Ok(value) => {
+                    normalized.insert(spec.name.to_string(), value);
+                }
+                Err(reason) => invalid_fields.push(InvalidSlot {
+                    field: spec.name.to_string(),
+                    value: value.clone(),
+                    reason,
+                }),
+            },
+        }
+    }
+
+    let alternatives = alternative_slots(&draft.intent);
+    if let Some(asked) = alternatives.last() {
+        if !alternatives.iter().any(|name| draft.slots.get(*name).is_some_and(|v| !v.is_null())) {
+            missing_fields.push(asked.to_string());
+        }
+    }
+
+    if missing_fields.is_empty() && invalid_fields.is_empty() {
+        return build_intent(draft, normalized);
+    }
+
+    let mut question = String::new();
+    for invalid in &invalid_fields {
+        question += &format!("The {} `{}` doesn't look right: {}. ", invalid.field.replace('_', " "), display_value(&invalid.value), invalid.reason);
+    }
+    let asks: Vec<&str> = specs
+        .iter()
+        .filter(|spec| missing_fields.iter().chain(invalid_fields.iter().map(|i| &i.field)).any(|f| f == spec.name))
+        .map(|spec| spec.question)
+        .filter(|question| !question.is_empty())
+        .collect();
+    question += &asks.join(" ");
+
+    Err(Box::new(Clarification {
+        intent: draft.intent.clone(),
+        question: question.trim().to_string(),
+        missing_fields,
+        invalid_fields,
+        draft,
+        choices: Vec::new(),
+    }))
+}
+
+/// The `Intent` for a draft whose slots all validated. Should it still not
+/// deserialize, the slot serde names (or else the first one) is asked again
+/// rather than the request being dropped.
+fn build_intent(draft: IntentDraft, normalized: Map<String, Value>) -> Result<Intent, Box<Clarification>> {
+    let specs = slot_specs(&draft.intent);
+    if specs.is_empty() {
+        // Only an intent name that is no `Intent` variant can fail here
+        return Ok(serde_json::from_value(json!(draft.intent)).unwrap_or_else(|e| {
+            println!("⚠️ Unknown intent {}: {}", draft.intent, e);
+            Intent::Unknown
+        }));
+    }
+
+    let error = match serde_json::from_value(json!({ draft.intent.as_str(): normalized })) {
+        Ok(intent) => return Ok(intent),
+        Err(e) => e.to_string(),
+    };
+    println!("⚠️ Could not build {} from {:?}: {}", draft.intent, normalized, error);
+    let field = specs
+        .iter()
+        .find(|spec| error.contains(&format!("`{}`", spec.name)))
+        .unwrap_or(&specs[0])
+        .name;
+    Err(reask_slot(draft, field, &format!("I couldn't use that {}", field.replace('_', " ")), Vec::new()))
+}
+
+/// Asks about one slot of an otherwise valid draft, e.g. a project name that
+/// matched nothing or several projects, offering `choices` when there are any.
+pub fn reask_slot(mut draft: IntentDraft, field: &str, reason: &str, choices: Vec<Choice>) -> Box<Clarification> {
+    let value = draft.slots.remove(field).unwrap_or(Value::Null);
+    let mut question = format!("{}.", reason);
+    if !choices.is_empty() {
+        for (i, choice) in choices.iter().enumerate() {
+            question += &format!("\n{}. {}", i + 1, choice.label);
+        }
+        question += "\nWhich one do you mean?";
+    } else if let Some(spec) = slot_specs(&draft.intent).iter().find(|spec| spec.name == field) {
+        question += &format!(" {}", spec.question);
+    }
+
+    Box::new(Clarification {
+        intent: draft.intent.clone(),
+        question,
+        missing_fields: Vec::new(),
+        invalid_fields: vec![InvalidSlot {
+            field: field.to_string(),
+            value,
+            reason: reason.to_string(),
+        }],
+        draft,
+        choices,
+    })
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn validate_slot(kind: SlotKind, value: &Value) -> Result<Value, String> {
+    match kind {
+        SlotKind::Project => {
+            let raw = value.as_str().ok_or("expected a project name, symbol or address")?.trim();
+            if raw.starts_with("0x") || raw.starts_with("0X") {
+                return Ok(Value::String(validate_address(raw)?));
+            }
+            let reference = raw.trim_start_matches('$').trim();
+            if reference.is_empty() {
+                return Err("expected a project name, symbol or address".to_string());
+            }
+            if reference.len() > MAX_QUERY_LEN {
+                return Err(format!("project names are limited to {} characters", MAX_QUERY_LEN));
+            }
+            Ok(Value::String(reference.to_string()))
+        }
+        SlotKind::Token => {
+            let raw = value.as_str().ok_or("expected a token contract address")?.trim();
+            Ok(Value::String(validate_address(raw)?))
+        }
+        SlotKind::Amount => {
+            let amount = match value {
+                Value::Number(_) => serde_json::from_value::<Amount>(value.clone()).map_err(|e| e.to_string())?,
+                // "0.5 BNB", "all", or a sentence holding one amount
+                Value::String(s) => match s.parse::<Amount>() {
+                    Ok(amount) => amount,
+                    Err(e) => single(rule_parser::find_amounts_in(s)).map(Amount::Exact).ok_or(e.to_string())?,
+                },
+                _ => return Err("expected an amount like 0.5".to_string()),
+            };
+            if amount.is_zero() {
+                return Err("the amount must be greater than zero".to_string());
+            }
+            Ok(json!(amount))
+        }
+        SlotKind::Symbol => {
+            let symbol = value.as_str().ok_or("expected a ticker symbol")?.trim().trim_start_matches('$');
+            if !(MIN_SYMBOL_LEN..=MAX_SYMBOL_LEN).contains(&symbol.len()) {
+                return Err(format!("symbols must be {}-{} characters", MIN_SYMBOL_LEN, MAX_SYMBOL_LEN));
+            }
+            if !symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err("symbols may only contain letters and digits".to_string());
+            }
+            Ok(Value::String(symbol.to_uppercase()))
+        }
+        SlotKind::Name => {
+            let name = value.as_str().ok_or("expected a name")?.trim();
+            if name.is_empty() {
+                return Err("the name must not be empty".to_string());
+            }
+            if name.len() > MAX_NAME_LEN {
+                return Err(format!("names are limited to {} characters", MAX_NAME_LEN));
+            }
+            Ok(Value::String(name.to_string()))
+        }
+        SlotKind::Query => {
+            let query = value.as_str().ok_or("expected a search term")?.trim().trim_matches('"');
+            if query.is_empty() {
+                return Err("the search term must not be empty".to_string());
+            }
+            if query.len() > MAX_QUERY_LEN {
+                return Err(format!("search terms are limited to {} characters", MAX_QUERY_LEN));
+            }
+            Ok(Value::String(query.to_string()))
+        }
+        SlotKind::Count => {
+            let count = match value {
+                // Amount parsing and some models produce 5.0 for 5
+                Value::Number(n) => n.as_u64().or_else(|| n.as_f64().filter(|f| f.fract() == 0.0 && *f >= 0.0).map(|f| f as u64)),
+                Value::String(s) => s.trim().parse::<u64>().ok(),
+                _ => None,
+            }
+            .ok_or("expected a whole number")?;
+            if !(1..=MAX_LISTING_LIMIT).contains(&count) {
+                return Err(format!("pick a number from 1 to {}", MAX_LISTING_LIMIT));
+            }
+            Ok(json!(count))
+        }
+        SlotKind::Status => {
+            let status = match value {
+                Value::Number(n) => n.as_u64().and_then(|n| u8::try_from(n).ok()).and_then(ProjectStatus::from_u8),
+                Value::String(s) => ProjectStatus::from_name(s),
+                _ => None,
+            }
+            .ok_or("unknown project status")?;
+            Ok(json!(status))
+        }
+        SlotKind::Cursor => {
+            let cursor = match value {
+                Value::Number(n) => n.as_u64().map(|n| n.to_string()),
+                Value::String(s) => Some(s.trim().to_string()).filter(|s| s.parse::<u64>().is_ok()),
+                _ => None,
+            }
+            .ok_or("expected a cursor from an earlier listing")?;
+            Ok(Value::String(cursor))
+        }
+    }
+}
+
+/// Parses a 0x address and returns its EIP-55 checksummed form. Mixed-case
+/// input must already carry a valid checksum; all-lower/upper input is accepted.
+pub fn validate_address(raw: &str) -> Result<String, String> {
+    let hex = raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")).ok_or("addresses start with 0x")?;
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("addresses are 0x followed by 40 hex characters".to_string());
+    }
+    let address = Address::from_str(hex).map_err(|e| e.to_string())?;
+    let checksummed = to_checksum(&address, None);
+
+    let mixed_case = hex.chars().any(|c| c.is_ascii_lowercase()) && hex.chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case && checksummed[2..] != *hex {
+        return Err("the address checksum does not match".to_string());
+    }
+    Ok(checksummed)
+}
+
+/// Fills the clarification's open slots from a free-text reply such as
+/// "0.3 BNB" or "0xabc...". Only unambiguous values are taken; a bare reply
+/// is used as the name or symbol when that is the single slot being asked for.
+pub fn fill_from_reply(clarification: &Clarification, reply: &str) -> IntentDraft {
+    let mut draft = clarification.draft.clone();
+    let open = clarification.open_fields();
+    let specs = slot_specs(&clarification.intent);
+    let text = reply.trim();
+
+    for field in &open {
+        let Some(spec) = specs.iter().find(|spec| spec.name == field) else { continue };
+        let value = match spec.kind {
+            SlotKind::Project => single(rule_parser::find_addresses_in(text))
+                .or_else(|| pick_choice(&clarification.choices, text))
+                .or_else(|| (open.len() == 1 && !text.is_empty()).then(|| text.to_string()))
+                .map(Value::String),
+            SlotKind::Token => single(rule_parser::find_addresses_in(text)).map(Value::String),
+            SlotKind::Amount => text
+                .parse::<Amount>()
+                .ok()
+                .or_else(|| single(rule_parser::find_amounts_in(text)).map(Amount::Exact))
+                .map(|amount| json!(amount)),
+            SlotKind::Symbol => {
+                let candidates: Vec<String> = text
+                    .split_whitespace()
+                    .map(|w| w.trim_matches(|c: char| !c.is_ascii_alphanumeric()))
+                    .filter(|w| (MIN_SYMBOL_LEN..=MAX_SYMBOL_LEN).contains(&w.len()))
+                    .filter(|w| w.chars().all(|c| c.is_ascii_alphanumeric()))
+                    .filter(|w| open.len() == 1 || w.chars().all(|c| !c.is_ascii_lowercase()))
+                    .map(str::to_string)
+                    .collect();
+                single(candidates).map(Value::String)
+            }
+            SlotKind::Name | SlotKind::Query if open.len() == 1 && !text.is_empty() => Some(Value::String(text.to_string())),
+            SlotKind::Status => single(
+                text.split_whitespace()
+                    .filter_map(|w| ProjectStatus::from_name(w.trim_matches(|c: char| !c.is_ascii_alphabetic())))
+                    .collect(),
+            )
+            .map(|status| json!(status)),
+            SlotKind::Name | SlotKind::Query | SlotKind::Count | SlotKind::Cursor => None,
+        };
+        if let Some(value) = value {
+            draft.slots.insert(field.clone(), value);
+        }
+    }
+    draft
+}
+
+/// A choice picked by position ("2", "the second one") or by a phrase that
+/// only one choice's label contains.
+fn pick_choice(choices: &[Choice], reply: &str) -> Option<String> {
+    if choices.is_empty() {
+        return None;
+    }
+    if let Some(position) = rule_parser::find_ordinal(reply) {
+        return choices.get(position.checked_sub(1)?).map(|choice| choice.value.clone());
+    }
+    let wanted = reply.trim().trim_start_matches('$').to_lowercase();
+    single(
+        choices
+            .iter()
+            .filter(|choice| !wanted.is_empty() && choice.label.to_lowercase().contains(&wanted))
+            .map(|choice| choice.value.clone())
+            .collect(),
+    )
+}
+
+fn single<T>(mut values: Vec<T>) -> Option<T> {
+    if values.len() == 1 { values.pop() } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::U256;
+
+    /// The EIP-55 test vector, checksummed.
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    fn draft(intent: &str, slots: Value) -> IntentDraft {
+        let Value::Object(slots) = slots else { panic!("slots must be an object") };
+        IntentDraft { intent: intent.to_string(), slots }
+    }
+
+    #[test]
+    fn validate_address_checksums_single_case_input() {
+        assert_eq!(validate_address(&CHECKSUMMED.to_lowercase()).as_deref(), Ok(CHECKSUMMED));
+        assert_eq!(validate_address(CHECKSUMMED).as_deref(), Ok(CHECKSUMMED));
+    }
+
+    #[test]
+    fn validate_address_rejects_bad_input() {
+        let wrong_case = CHECKSUMMED.replace("aA", "Aa");
+        assert!(validate_address(&wrong_case).unwrap_err().contains("checksum"));
+        assert!(validate_address("0x1234").is_err());
+        assert!(validate_address("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+        assert!(validate_address("0xZZZeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+    }
+
+    #[test]
+    fn resolves_a_complete_draft() {
+        let intent = resolve(draft("Invest", json!({
+            "project_id": CHECKSUMMED.to_lowercase(),
+            "amount": "0.5 BNB",
+        })))
+        .unwrap();
+        match intent {
+            Intent::Invest { project_id, amount } => {
+                assert_eq!(project_id, CHECKSUMMED);
+                assert_eq!(amount, Amount::Exact(U256::exp10(17) * 5));
+            }
+            other => panic!("expected Invest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn asks_for_missing_slots() {
+        let clarification = resolve(draft("Invest", json!({ "project_id": "PEPE" }))).unwrap_err();
+        assert_eq!(clarification.missing_fields, vec!["amount"]);
+        assert!(clarification.question.starts_with("How much"));
+    }
+
+    #[test]
+    fn reports_invalid_slots() {
+        let clarification = resolve(draft("CreateProject", json!({ "name": "Moon", "symbol": "M!" }))).unwrap_err();
+        assert_eq!(clarification.open_fields(), vec!["symbol"]);
+
+        let clarification = resolve(draft("Invest", json!({ "project_id": "PEPE", "amount": "0" }))).unwrap_err();
+        assert_eq!(clarification.invalid_fields[0].reason, "the amount must be greater than zero");
+    }
+
+    #[test]
+    fn needs_one_of_alternative_slots() {
+        let clarification = resolve(draft("GetTokenBalance", json!({}))).unwrap_err();
+        assert_eq!(clarification.missing_fields, vec!["project_id"]);
+
+        let intent = resolve(draft("GetTokenBalance", json!({ "token": CHECKSUMMED }))).unwrap();
+        assert!(matches!(intent, Intent::GetTokenBalance { token: Some(_), project_id: None }));
+    }
+
+    #[test]
+    fn resolves_intents_without_slots() {
+        assert!(matches!(resolve(IntentDraft::new("ListProjects")), Ok(Intent::ListProjects)));
+        assert!(matches!(resolve(IntentDraft::new("NoSuchIntent")), Ok(Intent::Unknown)));
+    }
+
+    #[test]
+    fn fills_the_open_slot_from_a_reply() {
+        let clarification = resolve(draft("Invest", json!({ "project_id": "PEPE" }))).unwrap_err();
+        let filled = fill_from_reply(&clarification, "0.3 BNB please");
+        assert_eq!(filled.slots.get("amount"), Some(&json!("0.3")));
+        assert!(resolve(filled).is_ok());
+    }
+}
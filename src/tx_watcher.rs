@@ -0,0 +1,224 @@
+use crate::agent::Intent;
+use crate::event_feed::EventFeed;
+use crate::indexer::{Claim, ClaimKind};
+use crate::intent_store::{IntentRecord, IntentState, IntentStore, ReceiptOutcome};
+use crate::launchpad_services::LaunchpadEvent;
+use crate::rpc::RpcProvider;
+use crate::web3::Web3Service;
+use anyhow::Result;
+use ethers::providers::Middleware;
+use ethers::types::{Address, TransactionReceipt, H256};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::AbortHandle;
+
+/// Blocks a transaction must be buried under before it counts as final.
+pub const DEFAULT_CONFIRMATIONS: u64 = 3;
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+/// A transaction with no receipt after this long was most likely dropped.
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Follows transactions the user broadcast from their own wallet and settles
+/// the matching intents once the chain has confirmed (or reverted) them.
+#[derive(Debug, Clone)]
+pub struct TxWatcher {
+    provider: RpcProvider,
+    web3_service: Web3Service,
+    intents: IntentStore,
+    confirmations: u64,
+    feed: Option<EventFeed>,
+    watches: Arc<Mutex<Vec<Watch>>>,
+}
+
+/// A transaction being followed, so `stop` can hand it to a new watcher.
+#[derive(Debug)]
+struct Watch {
+    intent_id: String,
+    tx_hash: H256,
+    task: AbortHandle,
+}
+
+impl TxWatcher {
+    pub fn new(web3_service: Web3Service, intents: IntentStore, confirmations: u64) -> Self {
+        Self {
+            provider: web3_service.provider(),
+            web3_service,
+            intents,
+            confirmations: confirmations.max(1),
+            feed: None,
+            watches: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Checks receipts on every new head from `feed` rather than on a timer.
+    pub fn with_feed(mut self, feed: &EventFeed) -> Self {
+        self.feed = Some(feed.clone());
+        self
+    }
+
+    pub fn confirmations(&self) -> u64 {
+        self.confirmations
+    }
+
+    /// Polls for the receipt in the background; the outcome lands in the
+    /// intent store, which broadcasts it to anyone following the intent.
+    pub fn watch(&self, intent_id: String, tx_hash: H256) {
+        let watcher = self.clone();
+        let id = intent_id.clone();
+        let handle = tokio::spawn(async move {
+            println!("👀 Watching {:?} for intent {} ({} confirmation(s))", tx_hash, intent_id, watcher.confirmations);
+
+            let update = match watcher.wait_for_receipt(tx_hash).await {
+                Some(outcome) => {
+                    for event in &outcome.events {
+                        println!("📜 {:?}: {}", tx_hash, describe(event));
+                    }
+                    watcher.intents.mark_receipt(&intent_id, outcome).await
+                }
+                None => {
+                    let reason = format!("no receipt after {} minutes", RECEIPT_TIMEOUT.as_secs() / 60);
+                    watcher.intents.mark_dropped(&intent_id, &reason).await
+                }
+            };
+
+            match update {
+                Ok(record) => {
+                    println!("✅ Intent {} settled as {:?}", intent_id, record.state);
+                    watcher.record_claim(&record, tx_hash).await;
+                }
+                Err(e) => println!("⚠️ Could not settle intent {}: {}", intent_id, e),
+            }
+        });
+
+        let mut watches = self.watches();
+        watches.retain(|watch| !watch.task.is_finished());
+        watches.push(Watch { intent_id: id, tx_hash, task: handle.abort_handle() });
+    }
+
+    /// Stops following every transaction and returns the ones still waiting
+    /// for a receipt, for the watcher that replaces this one.
+    pub fn stop(&self) -> Vec<(String, H256)> {
+        self.watches()
+            .drain(..)
+            .filter(|watch| !watch.task.is_finished())
+            .map(|watch| {
+                watch.task.abort();
+                (watch.intent_id, watch.tx_hash)
+            })
+            .collect()
+    }
+
+    fn watches(&self) -> std::sync::MutexGuard<'_, Vec<Watch>> {
+        self.watches.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    async fn wait_for_receipt(&self, tx_hash: H256) -> Option<ReceiptOutcome> {
+        let started = Instant::now();
+        let mut heads = self.feed.as_ref().map(EventFeed::heads).unwrap_or_default();
+        while started.elapsed() < RECEIPT_TIMEOUT {
+            match self.check(tx_hash).await {
+                Ok(Some(outcome)) => return Some(outcome),
+                Ok(None) => {}
+                // RPC hiccups are retried until the timeout
+                Err(e) => println!("⚠️ Receipt poll for {:?} failed: {}", tx_hash, e),
+            }
+            heads.wait(POLL_INTERVAL).await;
+        }
+        None
+    }
+
+    /// The outcome once the receipt has enough confirmations. The receipt is
+    /// fetched again on every poll, so a reorged-out block is noticed.
+    async fn check(&self, tx_hash: H256) -> Result<Option<ReceiptOutcome>> {
+        let Some(receipt) = self.provider.get_transaction_receipt(tx_hash).await? else {
+            return Ok(None);
+        };
+        let Some(block_number) = receipt.block_number else {
+            return Ok(None);
+        };
+
+        let head = self.provider.get_block_number().await?;
+        let confirmations = head.saturating_sub(block_number).as_u64() + 1;
+        if confirmations < self.confirmations {
+            return Ok(None);
+        }
+
+        Ok(Some(self.outcome(&receipt, block_number.as_u64(), confirmations)))
+    }
+
+    /// Records a confirmed claim right away, so investment history flags it
+    /// before the indexer reaches its block.
+    async fn record_claim(&self, record: &IntentRecord, tx_hash: H256) {
+        let (Some(receipt), IntentState::Confirmed) = (&record.receipt, record.state) else {
+            return;
+        };
+        let (kind, project_id) = match &record.intent {
+            Some(Intent::ClaimTokens { project_id }) => (ClaimKind::Tokens, project_id),
+            Some(Intent::ClaimRefund { project_id }) => (ClaimKind::Refund, project_id),
+            _ => return,
+        };
+        let Ok(project) = Address::from_str(project_id) else {
+            return;
+        };
+
+        let claim = Claim {
+            project,
+            account: receipt.from,
+            kind,
+            tx_hash,
+            block_number: receipt.block_number,
+        };
+        if let Err(e) = self.web3_service.record_claim(claim).await {
+            println!("⚠️ Could not record claim {:?}: {}", tx_hash, e);
+        }
+    }
+
+    fn outcome(&self, receipt: &TransactionReceipt, block_number: u64, confirmations: u64) -> ReceiptOutcome {
+        ReceiptOutcome {
+            // Receipts without a status predate Byzantium and only exist for successful transactions
+            status: receipt.status.map(|s| s.as_u64()).unwrap_or(1),
+            from: receipt.from,
+            block_number,
+            confirmations,
+            gas_used: receipt.gas_used.map(|gas| gas.to_string()),
+            events: self.web3_service.decode_launchpad_events(&receipt.logs),
+        }
+    }
+}
+
+fn describe(event: &LaunchpadEvent) -> String {
+    match event {
+        LaunchpadEvent::ProjectCreated { project, name, .. } => format!("project '{}' created at {:?}", name, project),
+        LaunchpadEvent::Invested { project, investor, amount, .. } => {
+            format!("{:?} invested {} wei in {:?}", investor, amount, project)
+        }
+        LaunchpadEvent::ProjectStatusChanged { project, status } => format!("{:?} moved to status {}", project, status),
+        LaunchpadEvent::TokensClaimed { project, investor, .. } => format!("{:?} claimed tokens from {:?}", investor, project),
+        LaunchpadEvent::RefundClaimed { project, investor, amount } => {
+            format!("{:?} was refunded {} wei by {:?}", investor, amount, project)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn stop_hands_over_unsettled_watches() {
+        // Nothing answers on this port, so the receipt never arrives
+        let web3_service = Web3Service::new_without_signer(
+            "http://127.0.0.1:9",
+            "0x0000000000000000000000000000000000000001",
+            97,
+        )
+        .unwrap();
+        let watcher = TxWatcher::new(web3_service, IntentStore::new(), DEFAULT_CONFIRMATIONS);
+        let tx_hash = H256::repeat_byte(0x42);
+        watcher.watch("intent".to_string(), tx_hash);
+
+        assert_eq!(watcher.stop(), vec![("intent".to_string(), tx_hash)]);
+        assert!(watcher.stop().is_empty());
+    }
+}
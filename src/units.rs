@@ -0,0 +1,296 @@
+use ethers::types::U256;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Decimals of ether, BNB and every other native currency.
+pub const ETHER_DECIMALS: u32 = 18;
+pub const GWEI_DECIMALS: u32 = 9;
+/// Most decimals a U256 amount can be scaled by without every value overflowing.
+const MAX_DECIMALS: u32 = 77;
+
+/// Native-currency unit words and their decimals relative to wei.
+const NATIVE_UNITS: &[(&str, u32)] = &[
+    ("bnb", 18),
+    ("tbnb", 18),
+    ("eth", 18),
+    ("ether", 18),
+    ("finney", 15),
+    ("szabo", 12),
+    ("gwei", 9),
+    ("wei", 0),
+];
+
+/// Shorthand multipliers: "1.5k" is 1,500.
+const MAGNITUDES: &[(&str, u32)] = &[("k", 3), ("m", 6), ("b", 9)];
+
+/// Words asking for the whole spendable balance.
+const ALL_WORDS: &[&str] = &["all", "max", "everything"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitsError {
+    Invalid(String),
+    TooPrecise { decimals: u32 },
+    Overflow,
+}
+
+impl fmt::Display for UnitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitsError::Invalid(text) => write!(f, "\"{}\" is not an amount", text),
+            UnitsError::TooPrecise { decimals } => write!(f, "amounts have at most {} decimal places", decimals),
+            UnitsError::Overflow => write!(f, "the amount is too large"),
+        }
+    }
+}
+
+impl std::error::Error for UnitsError {}
+
+/// Parses a plain decimal such as "1.5" into base units of a token with
+/// `decimals` decimals, exactly. Fractions finer than one base unit are an
+/// error rather than being rounded away.
+pub fn parse_units(number: &str, decimals: u32) -> Result<U256, UnitsError> {
+    let invalid = || UnitsError::Invalid(number.to_string());
+    if decimals > MAX_DECIMALS {
+        return Err(UnitsError::Overflow);
+    }
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid());
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(UnitsError::TooPrecise { decimals });
+    }
+    let digits = format!("{}{}{}", whole, fraction, "0".repeat(decimals as usize - fraction.len()));
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(U256::zero());
+    }
+    U256::from_dec_str(digits).map_err(|_| UnitsError::Overflow)
+}
+
+/// Base units as a decimal with `decimals` decimals and no trailing zeros:
+/// "1.5", "10", "0.000001".
+pub fn format_units(value: U256, decimals: u32) -> String {
+    let digits = value.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Like `format_units`, cut (not rounded) to at most `places` decimal places,
+/// so a balance is never shown as more than it is.
+pub fn format_units_truncated(value: U256, decimals: u32, places: u32) -> String {
+    let cut = decimals.saturating_sub(places);
+    let unit = U256::exp10(cut as usize);
+    format_units(value / unit * unit, decimals)
+}
+
+pub fn format_ether(wei: U256) -> String {
+    format_units(wei, ETHER_DECIMALS)
+}
+
+pub fn format_gwei(wei: U256) -> String {
+    format_units(wei, GWEI_DECIMALS)
+}
+
+/// Decimals of a native unit word such as "BNB" or "gwei".
+pub fn native_unit_decimals(unit: &str) -> Option<u32> {
+    let unit = unit.to_lowercase();
+    NATIVE_UNITS.iter().find(|(name, _)| *name == unit).map(|(_, decimals)| *decimals)
+}
+
+pub fn is_all_word(word: &str) -> bool {
+    ALL_WORDS.contains(&word.to_lowercase().as_str())
+}
+
+/// Parses a native amount written as one token with an optional unit glued
+/// on ("0.5", "1.5k", "0.5bnb", "500gwei") or followed by a separate unit
+/// word. Bare numbers are whole native units. `None` when `token` is no amount.
+pub fn parse_native(token: &str, unit: Option<&str>) -> Option<Result<U256, UnitsError>> {
+    let lower = token.to_lowercase();
+    let split = lower
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(lower.len());
+    let (number, suffix) = lower.split_at(split);
+    if number.is_empty() || number == "." {
+        return None;
+    }
+
+    // A unit glued on wins over one that follows: "0.5bnb"
+    if let Some(decimals) = native_unit_decimals(suffix) {
+        return Some(parse_units(number, decimals));
+    }
+    let magnitude = MAGNITUDES.iter().find(|(name, _)| *name == suffix).map(|(_, m)| *m);
+    if !suffix.is_empty() && magnitude.is_none() {
+        return None;
+    }
+    let decimals = unit.and_then(native_unit_decimals).unwrap_or(ETHER_DECIMALS);
+    Some(parse_units(number, decimals + magnitude.unwrap_or(0)))
+}
+
+/// A native-currency amount as a user asked for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Amount {
+    /// Exactly this many wei.
+    Exact(U256),
+    /// The whole balance, less what the transaction's gas may cost.
+    All,
+}
+
+impl Amount {
+    pub fn is_zero(&self) -> bool {
+        matches!(self, Amount::Exact(wei) if wei.is_zero())
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Amount::Exact(wei) => write!(f, "{}", format_ether(*wei)),
+            Amount::All => write!(f, "all"),
+        }
+    }
+}
+
+/// Text holding one amount: "0.5", "0.25 BNB", "1.5k", "500 gwei" or "all".
+impl FromStr for Amount {
+    type Err = UnitsError;
+
+    fn from_str(text: &str) -> Result<Self, UnitsError> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let parsed = match words.as_slice() {
+            [word] if is_all_word(word) => return Ok(Amount::All),
+            [token] => parse_native(token, None),
+            [token, unit] if native_unit_decimals(unit).is_some() => parse_native(token, Some(unit)),
+            _ => None,
+        };
+        match parsed {
+            Some(wei) => wei.map(Amount::Exact),
+            None => Err(UnitsError::Invalid(text.trim().to_string())),
+        }
+    }
+}
+
+/// Written as whole native units, e.g. "0.25", or "all".
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Read from text such as "0.25 BNB", or from a JSON number of whole native
+/// units, which is taken by its decimal digits rather than as a float.
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(text) => text.parse().map_err(serde::de::Error::custom),
+            serde_json::Value::Number(number) => parse_json_number(&number.to_string())
+                .map(Amount::Exact)
+                .map_err(serde::de::Error::custom),
+            other => Err(serde::de::Error::custom(format!("expected an amount, got {}", other))),
+        }
+    }
+}
+
+/// Whole native units as JSON writes them, which may be "1e-5".
+fn parse_json_number(text: &str) -> Result<U256, UnitsError> {
+    let (mantissa, exponent) = text.split_once(['e', 'E']).unwrap_or((text, "0"));
+    let exponent: i64 = exponent.parse().map_err(|_| UnitsError::Invalid(text.to_string()))?;
+    let decimals = u32::try_from(ETHER_DECIMALS as i64 + exponent)
+        .map_err(|_| UnitsError::TooPrecise { decimals: ETHER_DECIMALS })?;
+    parse_units(mantissa, decimals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wei(digits: &str) -> U256 {
+        U256::from_dec_str(digits).unwrap()
+    }
+
+    #[test]
+    fn parse_native_reads_bare_numbers_as_whole_units() {
+        assert_eq!(parse_native("0.5", None), Some(Ok(wei("500000000000000000"))));
+        assert_eq!(parse_native("2", None), Some(Ok(wei("2000000000000000000"))));
+        assert_eq!(parse_native(".25", None), Some(Ok(wei("250000000000000000"))));
+    }
+
+    #[test]
+    fn parse_native_applies_glued_and_separate_units() {
+        assert_eq!(parse_native("0.5bnb", None), Some(Ok(wei("500000000000000000"))));
+        assert_eq!(parse_native("500gwei", None), Some(Ok(wei("500000000000"))));
+        assert_eq!(parse_native("500", Some("gwei")), Some(Ok(wei("500000000000"))));
+        assert_eq!(parse_native("7", Some("wei")), Some(Ok(wei("7"))));
+    }
+
+    #[test]
+    fn parse_native_applies_magnitudes() {
+        assert_eq!(parse_native("1.5k", None), Some(Ok(wei("1500000000000000000000"))));
+        assert_eq!(parse_native("2m", None), Some(Ok(wei("2000000000000000000000000"))));
+    }
+
+    #[test]
+    fn parse_native_rejects_what_is_no_amount() {
+        assert_eq!(parse_native("pepe", None), None);
+        assert_eq!(parse_native("12abc", None), None);
+        assert_eq!(parse_native(".", None), None);
+    }
+
+    #[test]
+    fn parse_native_refuses_to_round() {
+        assert_eq!(parse_native("1.5", Some("wei")), Some(Err(UnitsError::TooPrecise { decimals: 0 })));
+        assert_eq!(
+            parse_native("0.0000000000000000001", None),
+            Some(Err(UnitsError::TooPrecise { decimals: ETHER_DECIMALS }))
+        );
+    }
+
+    #[test]
+    fn format_units_trims_trailing_zeros() {
+        assert_eq!(format_units(wei("1500000000000000000"), 18), "1.5");
+        assert_eq!(format_units(wei("10000000000000000000"), 18), "10");
+        assert_eq!(format_units(wei("1000000000000"), 18), "0.000001");
+        assert_eq!(format_units(U256::zero(), 18), "0");
+        assert_eq!(format_units(wei("1234"), 0), "1234");
+        assert_eq!(format_units(wei("1234"), 2), "12.34");
+    }
+
+    #[test]
+    fn format_units_round_trips_parse_units() {
+        for text in ["0.5", "1", "123.456789", "0.000000000000000001"] {
+            assert_eq!(format_units(parse_units(text, 18).unwrap(), 18), text);
+        }
+    }
+
+    #[test]
+    fn format_units_truncated_never_rounds_up() {
+        assert_eq!(format_units_truncated(wei("1999999999999999999"), 18, 6), "1.999999");
+        assert_eq!(format_units_truncated(wei("1500000000000000000"), 18, 6), "1.5");
+    }
+
+    #[test]
+    fn amount_parses_text_and_json() {
+        assert_eq!("all".parse::<Amount>(), Ok(Amount::All));
+        assert_eq!("0.25 BNB".parse::<Amount>(), Ok(Amount::Exact(wei("250000000000000000"))));
+        assert!("0.25 apples".parse::<Amount>().is_err());
+        let amount: Amount = serde_json::from_str("1e-5").unwrap();
+        assert_eq!(amount, Amount::Exact(wei("10000000000000")));
+        assert_eq!(serde_json::to_string(&Amount::Exact(wei("1500000000000000000"))).unwrap(), "\"1.5\"");
+    }
+}
@@ -1,5 +1,8 @@
+use crate::chains::ChainRegistry;
+use crate::rpc::{FailoverClient, RpcProvider};
 use ethers::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use anyhow::Result;
@@ -25,16 +28,23 @@ pub enum WalletType {
 #[derive(Debug, Clone)]
 pub struct WalletManager {
     pub current_wallet: Arc<RwLock<Option<WalletConnection>>>,
-    pub provider: Arc<Provider<Http>>,
+    chains: Arc<ChainRegistry>,
+    // One per registry chain, keyed by chain id
+    providers: HashMap<u64, Arc<RpcProvider>>,
 }
 
 impl WalletManager {
-    pub fn new(provider_url: &str) -> Result<Self> {
-        let provider = Provider::<Http>::try_from(provider_url)?;
+    pub fn new(chains: Arc<ChainRegistry>) -> Result<Self> {
+        let mut providers = HashMap::new();
+        for chain in chains.chains() {
+            let provider = FailoverClient::new(&chain.rpc_urls)?.provider();
+            providers.insert(chain.chain_id, Arc::new(provider));
+        }
         
         Ok(Self {
             current_wallet: Arc::new(RwLock::new(None)),
-            provider: Arc::new(provider),
+            chains,
+            providers,
         })
     }
     
@@ -44,10 +54,11 @@ impl WalletManager {
         chain_id: u64,
         wallet_type: WalletType,
     ) -> Result<()> {
+        let chain = self.chains.get(chain_id)?;
         let connection = WalletConnection {
             address: address.clone(),
             chain_id,
-            provider_url: self.provider.url().to_string(),
+            provider_url: chain.rpc_url()?.to_string(),
             connected_at: chrono::Utc::now(),
             wallet_type,
         };
@@ -55,7 +66,7 @@ impl WalletManager {
         let mut wallet = self.current_wallet.write().await;
         *wallet = Some(connection);
         
-        println!("✅ Wallet connected: {} on chain {}", address, chain_id);
+        println!("✅ Wallet connected: {} on {} ({})", address, chain.name, chain_id);
         Ok(())
     }
     
@@ -72,7 +83,9 @@ impl WalletManager {
         match wallet.as_ref() {
             Some(conn) => {
                 let address: Address = conn.address.parse()?;
-                let balance = self.provider.get_balance(address, None).await?;
+                let provider = self.providers.get(&conn.chain_id)
+                    .ok_or_else(|| anyhow::anyhow!("No provider for chain {}", conn.chain_id))?;
+                let balance = provider.get_balance(address, None).await?;
                 Ok(balance)
             }
             None => Err(anyhow::anyhow!("No wallet connected")),
@@ -1,5 +1,7 @@
-use ethers::types::{U256, Address};
-use ethers::providers::{Provider, Http};
+use ethers::contract::EthEvent;
+use ethers::types::{U256, Address, Bytes, Filter, Log, H256, TransactionRequest};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::providers::Middleware;
 use ethers::signers::{Wallet, Signer};
 use ethers::middleware::SignerMiddleware;
 use std::str::FromStr;
@@ -7,17 +9,37 @@ use anyhow::Result;
 use crate::rand;
 use std::sync::Arc;
 use anyhow::anyhow;
-use crate::launchpad_services::{LaunchpadClient, MechaLaunchpad, Project, ProjectDetails, UserInfo};
+use serde::Serialize;
+use crate::chains::ChainConfig;
+use crate::erc20::{TokenInfo, TokenReader};
+use crate::rpc::{EndpointHealth, FailoverClient, RpcProvider};
+use crate::simulation::{self, Simulation};
+use crate::gas::{FeeSuggestions, GasPlan, GasSpeed, GasStrategy, DEFAULT_GAS_LIMIT_BUFFER_PERCENT};
+use crate::indexer::{index_log, Claim, ClaimKind, EventStore};
+use crate::launchpad_services::{InvestedFilter, RefundClaimedFilter, TokensClaimedFilter, LaunchpadClient, LaunchpadEvent, MarketingInfo, MechaLaunchpad, Project, ProjectBatch, ProjectDetails, ProjectStatus, UserInfo, DEFAULT_MULTICALL_ADDRESS};
 
 #[derive(Debug, Clone)]
 pub struct Web3Service {
     launchpad_client: LaunchpadClient,
+    rpc: FailoverClient,
+    // Endpoints that must agree on `get_project_verified`
+    rpc_quorum: usize,
+    gas: GasStrategy,
     chain_id: u64,
+    /// Ticker of the chain's native currency, for amounts shown to users.
+    native_symbol: String,
     is_read_only: bool,
     provider_url: String, // Store provider URL for reconnection
+    event_store: Option<EventStore>,
+    // First block with launchpad events, where history scans start without an index
+    deployment_block: u64,
 }
 
-#[derive(Debug, Clone)]
+/// Currency named in messages when the service was not built from a registry chain.
+const DEFAULT_NATIVE_SYMBOL: &str = "ETH";
+
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Investment {
     pub investor: Address,
     pub project_id: Address,
@@ -28,12 +50,6 @@ pub struct Investment {
     pub tokens_claimed: bool,
 }
 
-#[derive(Debug, Clone)]
-pub struct TokenData {
-    pub address: String,
-    pub name: String,
-    pub symbol: String
-}
 
 impl Web3Service {
     pub fn new(
@@ -45,24 +61,56 @@ impl Web3Service {
         let contract_addr = Address::from_str(contract_address)?;
         let launchpad_client = LaunchpadClient::new(provider_url, contract_addr, private_key, chain_id)?;
         
+        let rpc = FailoverClient::new(&[provider_url])?;
+        
         Ok(Self {
             launchpad_client,
+            gas: GasStrategy::new(rpc.provider(), None, DEFAULT_GAS_LIMIT_BUFFER_PERCENT),
+            rpc,
+            rpc_quorum: 1,
             chain_id,
+            native_symbol: DEFAULT_NATIVE_SYMBOL.to_string(),
             is_read_only: false,
             provider_url: provider_url.to_string(),
+            event_store: None,
+            deployment_block: 0,
         })
     }
     
+    /// Read-only service for a registry chain with a launchpad deployment.
+    pub fn for_chain(chain: &ChainConfig) -> Result<Self> {
+        let launchpad_address = chain.launchpad_address.as_deref()
+            .ok_or_else(|| anyhow!("No launchpad deployed on {} ({})", chain.name, chain.chain_id))?;
+        let rpc = FailoverClient::new(&chain.rpc_urls)?;
+        let mut service = Self::read_only(rpc, chain.rpc_url()?, launchpad_address, chain.chain_id)?;
+        service.set_multicall_address(&chain.multicall_address)?;
+        service.rpc_quorum = chain.rpc_quorum;
+        service.native_symbol = chain.native_symbol.clone();
+        service.deployment_block = chain.deployment_block;
+        service.gas = GasStrategy::new(service.rpc.provider(), chain.fee_model, chain.gas_limit_buffer_percent);
+        Ok(service)
+    }
+    
     pub fn new_without_signer(
         provider_url: &str,
         contract_address: &str,
+        chain_id: u64,
+    ) -> Result<Self> {
+        Self::read_only(FailoverClient::new(&[provider_url])?, provider_url, contract_address, chain_id)
+    }
+    
+    fn read_only(
+        rpc: FailoverClient,
+        provider_url: &str,
+        contract_address: &str,
+        chain_id: u64,
     ) -> Result<Self> {
         // Create a dummy wallet for read-only operations
         // The wallet won't actually sign anything
         let dummy_key = ethers::core::k256::ecdsa::SigningKey::random(&mut rand::thread_rng());
         let dummy_wallet = Wallet::from(dummy_key);
         
-        let provider = Provider::<Http>::try_from(provider_url)?;
+        let provider = rpc.provider();
         let signer_middleware = SignerMiddleware::new(provider, dummy_wallet);
         let client = Arc::new(signer_middleware);
         
@@ -73,25 +121,73 @@ impl Web3Service {
         let launchpad_client = LaunchpadClient {
             client,
             contract,
+            multicall: DEFAULT_MULTICALL_ADDRESS.parse()?,
         };
         
         Ok(Self {
             launchpad_client,
-            chain_id: 97,
+            gas: GasStrategy::new(rpc.provider(), None, DEFAULT_GAS_LIMIT_BUFFER_PERCENT),
+            rpc,
+            rpc_quorum: 1,
+            chain_id,
+            native_symbol: DEFAULT_NATIVE_SYMBOL.to_string(),
             is_read_only: true,
             provider_url: provider_url.to_string(),
+            event_store: None,
+            deployment_block: 0,
         })
     }
     pub async fn get_contract_address(&self) -> Result<String> {
         Ok(format!("{:?}", self.launchpad_client.contract.address()))
     }
+    
+    pub fn launchpad_address(&self) -> Address {
+        self.launchpad_client.contract_address()
+    }
+    
+    pub fn invest_calldata(&self, project_id: &str) -> Result<Bytes> {
+        let project_addr = Address::from_str(project_id)?;
+        self.launchpad_client.invest_calldata(project_addr)
+    }
+    
+    pub fn claim_tokens_calldata(&self, project_id: &str) -> Result<Bytes> {
+        let project_addr = Address::from_str(project_id)?;
+        self.launchpad_client.claim_tokens_calldata(project_addr)
+    }
+    
+    pub fn complete_project_calldata(&self, project_id: &str) -> Result<Bytes> {
+        let project_addr = Address::from_str(project_id)?;
+        self.launchpad_client.complete_project_calldata(project_addr)
+    }
+    
+    pub fn claim_refund_calldata(&self, project_id: &str) -> Result<Bytes> {
+        let project_addr = Address::from_str(project_id)?;
+        self.launchpad_client.claim_refund_calldata(project_addr)
+    }
+    
+    pub fn create_project_calldata(
+        &self,
+        creator: Address,
+        token_name: &str,
+        token_symbol: &str,
+        token_decimals: u8,
+        initial_supply: U256,
+    ) -> Result<Bytes> {
+        self.launchpad_client.create_project_calldata(
+            creator,
+            token_name.to_string(),
+            token_symbol.to_string(),
+            token_decimals,
+            initial_supply,
+        )
+    }
     pub async fn set_wallet_signer(&mut self, private_key: &str, chain_id: u64) -> Result<()> {
         // Create new wallet
         let wallet: Wallet<ethers::core::k256::ecdsa::SigningKey> = private_key.parse()?;
         let wallet_with_chain = wallet.with_chain_id(chain_id);
         
-        // Create new provider (can't extract from existing client easily)
-        let provider = Provider::<Http>::try_from(&self.provider_url)?;
+        // Same endpoints, so failover and health carry over to the signer
+        let provider = self.rpc.provider();
         
         // Create new signer middleware
         let signer_middleware = SignerMiddleware::new(provider, wallet_with_chain);
@@ -105,6 +201,7 @@ impl Web3Service {
         self.launchpad_client = LaunchpadClient {
             client,
             contract,
+            multicall: self.launchpad_client.multicall,
         };
         
         self.chain_id = chain_id;
@@ -113,72 +210,112 @@ impl Web3Service {
         Ok(())
     }
     
+    /// Slow, normal and fast fees for the next block.
+    pub async fn suggest_fees(&self) -> Result<FeeSuggestions> {
+        self.gas.suggest_fees().await
+    }
+    
+    /// Gas limit and fees for a launchpad call sent from `from`, or from an
+    /// unknown sender when `None`.
+    pub async fn plan_gas(
+        &self,
+        from: Option<Address>,
+        data: &Bytes,
+        value: U256,
+        fallback_limit: u64,
+    ) -> Result<GasPlan> {
+        let tx = self.launchpad_tx(from, data, value);
+        self.gas.plan(&tx, GasSpeed::Normal, fallback_limit).await
+    }
+    
+    pub async fn native_balance(&self, address: Address) -> Result<U256> {
+        Ok(self.rpc.provider().get_balance(address, None).await?)
+    }
+    
+    /// Runs a launchpad call from `from` against the latest block, to learn
+    /// whether it would revert before anyone signs it.
+    pub async fn simulate(&self, from: Address, data: &Bytes, value: U256) -> Simulation {
+        let tx = self.launchpad_tx(Some(from), data, value);
+        simulation::simulate(&self.rpc.provider(), &tx).await
+    }
+    
+    fn launchpad_tx(&self, from: Option<Address>, data: &Bytes, value: U256) -> TypedTransaction {
+        let mut tx = TransactionRequest::new()
+            .to(self.launchpad_address())
+            .data(data.clone())
+            .value(value)
+            .chain_id(self.chain_id);
+        if let Some(from) = from {
+            tx = tx.from(from);
+        }
+        tx.into()
+    }
+    
+    /// Multicall3 deployment used for batched reads on this chain.
+    pub fn set_multicall_address(&mut self, multicall_address: &str) -> Result<()> {
+        self.launchpad_client.multicall = Address::from_str(multicall_address)?;
+        Ok(())
+    }
+    
+    pub fn multicall_address(&self) -> Address {
+        self.launchpad_client.multicall
+    }
+    
+    /// Local index of launchpad events, which investment history reads from.
+    pub fn set_event_store(&mut self, event_store: EventStore) {
+        self.event_store = Some(event_store);
+    }
+    
     // Update provider URL if needed
     pub fn set_provider_url(&mut self, provider_url: &str) {
         self.provider_url = provider_url.to_string();
     }
     
-    pub async fn get_all_projects(&self) -> Result<Vec<Project>> {
+    pub async fn get_all_projects(&self) -> Result<ProjectBatch> {
         let project_addresses = self.launchpad_client.get_all_projects().await?;
-        
-        let mut projects = Vec::new();
-        for addr in project_addresses {
-            if let Ok(project) = self.launchpad_client.get_project_details(addr).await {
-                projects.push(project);
-            }
-        }
-        Ok(projects)
+        Ok(self.launchpad_client.get_projects_batched(&project_addresses).await)
     }
     
-    pub async fn get_trending_projects(&self, limit: u64) -> Result<Vec<Project>> {
+    pub async fn get_trending_projects(&self, limit: u64) -> Result<ProjectBatch> {
         let project_addresses = self.launchpad_client.get_trending_projects(limit).await?;
-        
-        let mut projects = Vec::new();
-        for addr in project_addresses {
-            if let Ok(project) = self.launchpad_client.get_project_details(addr).await {
-                projects.push(project);
-            }
-        }
-        Ok(projects)
+        Ok(self.launchpad_client.get_projects_batched(&project_addresses).await)
     }
     
-    pub async fn get_newly_launched_projects(&self, limit: u64) -> Result<Vec<Project>> {
+    pub async fn get_newly_launched_projects(&self, limit: u64) -> Result<ProjectBatch> {
         let project_addresses = self.launchpad_client.get_newly_launched_projects(limit).await?;
-        
-        let mut projects = Vec::new();
-        for addr in project_addresses {
-            if let Ok(project) = self.launchpad_client.get_project_details(addr).await {
-                projects.push(project);
-            }
-        }
-        Ok(projects)
+        Ok(self.launchpad_client.get_projects_batched(&project_addresses).await)
     }
     
-    pub async fn get_projects_by_status(&self, status: u8, page: u64, page_size: u64) -> Result<Vec<Project>> {
+    pub async fn get_projects_by_status(&self, status: u8, page: u64, page_size: u64) -> Result<ProjectBatch> {
         let project_addresses = self.launchpad_client.get_projects_by_status(status, page, page_size).await?;
-        
-        let mut projects = Vec::new();
-        for addr in project_addresses {
-            if let Ok(project) = self.launchpad_client.get_project_details(addr).await {
-                projects.push(project);
-            }
-        }
-        Ok(projects)
+        Ok(self.launchpad_client.get_projects_batched(&project_addresses).await)
     }
     
-    pub async fn get_project_statistics(&self) -> Result<(u64, u64, u64, u64, u64)> {
-        let stats = self.launchpad_client.get_project_statistics().await?;
-        Ok((
-            stats.0,
-            stats.1,
-            stats.2,
-            stats.3.as_u64(),
-            stats.4,
-        ))
+    /// Total, active and completed projects, total raised in wei, and investors.
+    pub async fn get_project_statistics(&self) -> Result<(u64, u64, u64, U256, u64)> {
+        self.launchpad_client.get_project_statistics().await
     }
     
-    pub async fn get_active_projects(&self) -> Result<Vec<Project>> {
-        self.get_projects_by_status(3, 1, 50).await
+    pub async fn get_active_projects(&self) -> Result<ProjectBatch> {
+        self.get_projects_by_status(ProjectStatus::Active.as_u8(), 1, 50).await
+    }
+    
+    // Address-only listings, so callers can page before fetching details
+    
+    pub async fn search_project_addresses(&self, query: &str) -> Result<Vec<Address>> {
+        self.launchpad_client.search_projects(query.to_string()).await
+    }
+    
+    pub async fn trending_project_addresses(&self, limit: u64) -> Result<Vec<Address>> {
+        self.launchpad_client.get_trending_projects(limit).await
+    }
+    
+    pub async fn newly_launched_project_addresses(&self, limit: u64) -> Result<Vec<Address>> {
+        self.launchpad_client.get_newly_launched_projects(limit).await
+    }
+    
+    pub async fn get_projects(&self, addresses: &[Address]) -> ProjectBatch {
+        self.launchpad_client.get_projects_batched(addresses).await
     }
     
     pub async fn get_project(&self, project_id: &str) -> Result<Option<Project>> {
@@ -189,6 +326,18 @@ impl Web3Service {
         }
     }
     
+    /// Like `get_project`, but read through the chain's RPC quorum, for
+    /// figures a transaction is about to be prepared on.
+    pub async fn get_project_verified(&self, project_id: &str) -> Result<Option<Project>> {
+        if self.rpc_quorum <= 1 {
+            return self.get_project(project_id).await;
+        }
+        let target_addr = Address::from_str(project_id)?;
+        let project = self.launchpad_client.get_project_quorum(target_addr, &self.rpc, self.rpc_quorum).await?;
+        // A zero creator means no project exists at that address
+        Ok(Some(project).filter(|project| !project.creator.is_zero()))
+    }
+    
     pub async fn get_project_full_details(&self, project_id: &str) -> Result<Option<(Project, ProjectDetails)>> {
         let project_addr = Address::from_str(project_id)?;
         
@@ -207,14 +356,19 @@ impl Web3Service {
         }
     }
     
-    pub async fn invest_in_project(&self, project_id: &str, amount_eth: f64) -> Result<String> {
+    /// `None` when the contract knows no marketing allocation for the
+    /// project, which it reports as an all-zero record.
+    pub async fn get_project_marketing_info(&self, project_id: &str) -> Result<Option<MarketingInfo>> {
+        let target_addr = Address::from_str(project_id)?;
+        let info = self.launchpad_client.get_project_marketing_info(target_addr).await?;
+        Ok(Some(info).filter(|info| !info.is_empty()))
+    }
+    
+    pub async fn invest_in_project(&self, project_id: &str, amount: U256) -> Result<String> {
         if self.is_read_only {
             return Err(anyhow!("Cannot invest: Web3Service is in read-only mode. Connect wallet first."));
         }
         
-        let amount_wei = (amount_eth * 1e18) as u128;
-        let amount = U256::from(amount_wei);
-        
         let project_addr = project_id.parse::<Address>()
             .map_err(|e| anyhow::anyhow!("Invalid project address: {}", e))?;
         
@@ -238,47 +392,6 @@ impl Web3Service {
         }
     }
     
-    pub async fn create_project(
-        &self,
-        name: String,
-        symbol: String,
-        soft_cap: U256,
-        hard_cap: U256,
-        end_time: U256,
-     ) -> Result<String> {
-        if self.is_read_only {
-            return Err(anyhow!("Cannot create project: Web3Service is in read-only mode. Connect wallet first."));
-        }
-        
-        use chrono::Utc;
-        
-        let creator = Address::zero();
-        
-        let token_name = format!("{} Token", name);
-        let token_symbol = symbol.clone();
-        let token_decimals = 18;
-        let initial_supply = hard_cap * U256::from(2);
-        
-        let project_id = self.launchpad_client.create_project(
-            creator,
-            token_name.clone(),
-            token_symbol.clone(),
-            token_decimals,
-            initial_supply,
-        ).await?;
-        
-        Ok(format!(
-            "✅ Project created successfully!\n\
-             💰 Token Address: {}\n\
-             🏷️ Token Name: {}\n\
-             🔠 Token Symbol: {}\n\
-             ",
-            project_id,
-            token_name,
-            token_symbol
-        ))
-    }
-    
     pub async fn register_user(&self, _telegram_id: u64, _telegram_username: &str) -> Result<String> {
         log::warn!("register_user not implemented in LaunchpadClient");
         Ok("User registration not implemented".to_string())
@@ -291,14 +404,6 @@ impl Web3Service {
         }
     }
     
-    pub async fn listen_project_created(&self) -> Result<()> {
-        self.launchpad_client.listen_project_created().await
-    }
-    
-    pub async fn listen_investments(&self) -> Result<()> {
-        self.launchpad_client.listen_investments().await
-    }
-    
     pub async fn check_connection(&self) -> Result<u64> {
         match self.get_project_statistics().await {
             Ok(_) => Ok(1),
@@ -308,30 +413,130 @@ impl Web3Service {
     
     pub async fn get_project_investment(
         &self,
-        _project_id: &str,
-        _user_address: &str,
+        project_id: &str,
+        user_address: &str,
      ) -> Result<Option<Investment>> {
-        log::warn!("get_project_investment not implemented");
-        Ok(None)
+        let project = Address::from_str(project_id)?;
+        let investor = Address::from_str(user_address)?;
+        let investments = self.investments_of(investor, Some(project)).await?;
+        
+        // Every contribution to the project, as one position
+        Ok(investments.into_iter().reduce(|total, next| Investment {
+            amount: total.amount.saturating_add(next.amount),
+            token_amount: total.token_amount.saturating_add(next.token_amount),
+            timestamp: next.timestamp,
+            refunded: total.refunded || next.refunded,
+            tokens_claimed: total.tokens_claimed || next.tokens_claimed,
+            ..total
+        }))
     }
     
-    pub async fn get_user_investments(&self, _user_address: &str) -> Result<Vec<Investment>> {
-        log::warn!("get_user_investments not implemented");
-        Ok(vec![])
+    pub async fn get_user_investments(&self, user_address: &str) -> Result<Vec<Investment>> {
+        let investor = Address::from_str(user_address)?;
+        self.investments_of(investor, None).await
     }
     
-    pub async fn get_token_data(&self, address: &str) -> Result<Option<TokenData>> {
-        if let Some(project) = self.get_project(address).await? {
-            Ok(Some(TokenData {
-                address: address.to_string(),
-                name: project.name,
-                symbol: project.symbol
-            }))
-        } else {
-            Ok(None)
+    /// Records a confirmed claim so investment history can flag it.
+    pub async fn record_claim(&self, claim: Claim) -> Result<()> {
+        let Some(store) = self.event_store.clone() else {
+            return Ok(());
+        };
+        let contract = self.launchpad_address();
+        tokio::task::spawn_blocking(move || store.record_claim(contract, &claim)).await?
+    }
+    
+    /// `Invested` events by `investor` from the local index, plus a direct
+    /// `eth_getLogs` scan of the blocks it has not reached yet (all of them,
+    /// before its first checkpoint). An investment counts as refunded or
+    /// claimed when a matching claim event or confirmed claim follows it.
+    async fn investments_of(&self, investor: Address, project: Option<Address>) -> Result<Vec<Investment>> {
+        let store = self.event_store.clone().ok_or_else(|| anyhow!("Event index not available"))?;
+        let contract = self.launchpad_address();
+        let (mut indexed, mut claims, last_block) = tokio::task::spawn_blocking(move || -> Result<_> {
+            Ok((
+                store.investments(contract, investor, project)?,
+                store.claims(contract, investor)?,
+                store.last_block(contract)?,
+            ))
+        }).await??;
+        
+        let provider = self.provider();
+        let head = provider.get_block_number().await?.as_u64();
+        let from = last_block.map_or(self.deployment_block, |last| last + 1);
+        if head >= from {
+            let mut filter = Filter::new()
+                .address(contract)
+                .topic0(vec![InvestedFilter::signature(), TokensClaimedFilter::signature(), RefundClaimedFilter::signature()])
+                .topic2(H256::from(investor))
+                .from_block(from)
+                .to_block(head);
+            if let Some(project) = project {
+                filter = filter.topic1(H256::from(project));
+            }
+            // Without the full range the history would look complete while missing entries
+            let logs = provider.get_logs(&filter).await.map_err(|e| {
+                anyhow!("Could not scan blocks {}-{} the event index has not reached yet: {}", from, head, e)
+            })?;
+            for event in logs.iter().filter_map(|log| index_log(self, log)) {
+                match event.claim() {
+                    Some(claim) => claims.push(claim),
+                    None => indexed.push(event),
+                }
+            }
+        }
+        
+        Ok(indexed
+            .into_iter()
+            .filter_map(|indexed| {
+                let LaunchpadEvent::Invested { project, investor, amount, token_amount, timestamp, .. } = indexed.event else {
+                    return None;
+                };
+                let claimed = |kind: ClaimKind| {
+                    claims.iter().any(|claim| {
+                        claim.kind == kind && claim.project == project && claim.block_number >= indexed.block_number
+                    })
+                };
+                Some(Investment {
+                    investor,
+                    project_id: project,
+                    amount,
+                    token_amount,
+                    timestamp,
+                    refunded: claimed(ClaimKind::Refund),
+                    tokens_claimed: claimed(ClaimKind::Tokens),
+                })
+            })
+            .collect())
+    }
+    
+    /// The ERC-20 token a project offers, or `None` when there is no such
+    /// project or its token has not been deployed yet.
+    pub async fn get_token_data(&self, project_id: &str) -> Result<Option<TokenInfo>> {
+        match self.get_project(project_id).await? {
+            Some(project) if !project.offering_token.is_zero() => {
+                Ok(Some(self.token_info(project.offering_token).await?))
+            }
+            _ => Ok(None),
         }
     }
     
+    pub async fn token_info(&self, token: Address) -> Result<TokenInfo> {
+        self.tokens().info(token).await
+    }
+    
+    pub async fn token_balance(&self, token: Address, owner: Address) -> Result<U256> {
+        self.tokens().balance_of(token, owner).await
+    }
+    
+    /// How much of `owner`'s `token` the launchpad contract may move.
+    pub async fn token_allowance(&self, token: Address, owner: Address) -> Result<U256> {
+        self.tokens().allowance(token, owner, self.launchpad_address()).await
+    }
+    
+    fn tokens(&self) -> TokenReader {
+        TokenReader::new(self.rpc.provider(), self.multicall_address())
+    }
+    
     pub fn is_read_only(&self) -> bool {
         self.is_read_only
     }
@@ -340,9 +545,26 @@ impl Web3Service {
         self.chain_id
     }
     
+    pub fn native_symbol(&self) -> &str {
+        &self.native_symbol
+    }
+    
     pub fn provider_url(&self) -> &str {
         &self.provider_url
     }
+    
+    /// The provider behind the launchpad client, for raw RPC calls.
+    pub fn provider(&self) -> RpcProvider {
+        self.launchpad_client.client.provider().clone()
+    }
+    
+    pub fn rpc_health(&self) -> Vec<EndpointHealth> {
+        self.rpc.health()
+    }
+    
+    pub fn decode_launchpad_events(&self, logs: &[Log]) -> Vec<LaunchpadEvent> {
+        self.launchpad_client.decode_events(logs)
+    }
     pub async fn get_client_address(&self) -> Result<Address> {
         if self.is_read_only {
             Err(anyhow!("Client is read-only, no address available"))